@@ -4,10 +4,12 @@
 //! data at runtime.
 
 static_toml::static_toml! {
+    // Deriving serde traits keeps the original keys, like `maxRetries`
     #[derive(Debug, Clone, serde::Deserialize)]
     // Use `cow` to enable `Cow<'static, str>` instead of just `&'static str`
     #[static_toml(cow, root_mod = config)]
-    const DEFAULT_CONFIG = include_toml!("examples/config.toml");
+    // Resolve the path relative to this file instead of the crate root
+    const DEFAULT_CONFIG = include_toml!(source: "config.toml");
 }
 
 const INPUT_CONFIG: &str = r#"
@@ -15,6 +17,7 @@ const INPUT_CONFIG: &str = r#"
 string = "another value"
 int = 69
 array = [8, 0, 0, 8, 5]
+maxRetries = 5
 "#;
 
 fn main() {
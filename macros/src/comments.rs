@@ -0,0 +1,175 @@
+//! Collects the comments of TOML files to document the generated code.
+//!
+//! The comment block directly preceding a key or table header describes its
+//! value. The `comments` module reads these blocks via the format-preserving
+//! parser of `toml_edit`, so they can be attached as `#[doc]` to the generated
+//! fields, modules and types.
+
+use std::collections::HashMap;
+
+use toml_edit::{DocumentMut, Item, RawString, Table};
+
+use crate::toml_tokens::PathSegment;
+
+/// Collects the comments preceding the keys and table headers of the files.
+///
+/// The comments are keyed by the [`comment_key`] of their value.
+/// Later files override the values of earlier ones, so their comments take
+/// precedence as well.
+/// Only comments below `root` are collected, with paths relative to it.
+pub(crate) fn collect(files: &[&str], root: &[PathSegment]) -> HashMap<String, String> {
+    // `at` addresses items of arrays by their index, while the items of arrays
+    // of tables are collected as slice items
+    let root: Vec<PathSegment> = root
+        .iter()
+        .map(|segment| match segment {
+            PathSegment::Index(i) => PathSegment::Item(*i),
+            segment => segment.clone()
+        })
+        .collect();
+
+    let mut comments = HashMap::new();
+    for content in files.iter().rev() {
+        let Ok(document) = content.parse::<DocumentMut>()
+        else {
+            continue;
+        };
+        let mut collected = Vec::new();
+        collect_table(document.as_table(), &mut Vec::new(), &mut collected);
+        for (path, comment) in collected {
+            let Some(path) = path.strip_prefix(&root[..]).filter(|path| !path.is_empty())
+            else {
+                continue;
+            };
+            comments.entry(comment_key(path)).or_insert(comment);
+        }
+    }
+    comments
+}
+
+/// Key of the comment of the value at the path.
+///
+/// All items of an array share their data type and therefore their
+/// documentation, so array indices and entries of maps are replaced by `*`.
+pub(crate) fn comment_key(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) => key.as_str(),
+            PathSegment::Index(_) |
+            PathSegment::Item(_) |
+            PathSegment::Variant(_) |
+            PathSegment::Entry(_) => "*"
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Collects the comments of the keys of the table and its nested tables.
+fn collect_table(
+    table: &Table,
+    path: &mut Vec<PathSegment>,
+    comments: &mut Vec<(Vec<PathSegment>, String)>
+) {
+    for (key, item) in table.iter() {
+        path.push(PathSegment::Key(key.to_string()));
+
+        // Tables are documented at their header, values at their key
+        let prefix = match item {
+            Item::Table(table) => table.decor().prefix(),
+            Item::ArrayOfTables(tables) => tables.get(0).and_then(|table| table.decor().prefix()),
+            _ => table.key(key).and_then(|key| key.leaf_decor().prefix())
+        };
+        if let Some(comment) = prefix.and_then(comment) {
+            comments.push((path.clone(), comment));
+        }
+
+        match item {
+            Item::Table(table) => collect_table(table, path, comments),
+            Item::ArrayOfTables(tables) => {
+                for (i, table) in tables.iter().enumerate() {
+                    path.push(PathSegment::Item(i));
+                    collect_table(table, path, comments);
+                    path.pop();
+                }
+            }
+            _ => ()
+        }
+
+        path.pop();
+    }
+}
+
+/// Extracts the last block of comment lines from the decor preceding a key.
+///
+/// Blank lines separate blocks, so comments meant for a whole section are not
+/// attached to its first key.
+/// The decor ends with the indentation of the key, which is not a line of its
+/// own.
+/// Returns `None` if no comment directly precedes the key.
+fn comment(prefix: &RawString) -> Option<String> {
+    let (lines, _indentation) = prefix.as_str()?.rsplit_once('\n')?;
+    let mut block: Vec<&str> = Vec::new();
+    for line in lines.lines().map(str::trim) {
+        match line.strip_prefix('#') {
+            Some(comment) => block.push(comment.strip_prefix(' ').unwrap_or(comment)),
+            None if line.is_empty() => block.clear(),
+            None => ()
+        }
+    }
+
+    match block.is_empty() {
+        true => None,
+        false => Some(block.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_works() {
+        let base = "\
+# Section comment
+
+# Name of the product
+name = \"static-toml\"
+
+# Database settings
+# used by the server
+[database]
+port = 8000 # not above the key
+  # Indented
+  host = \"localhost\"
+
+[[servers]]
+# Address of the server
+ip = \"10.0.0.1\"
+
+[[servers]]
+# Ignored, the first item documents the data type
+ip = \"10.0.0.2\"
+";
+        let overlay = "\
+# Overridden name
+name = \"other\"
+";
+
+        let comments = collect(&[base, overlay], &[]);
+        assert_eq!(comments["name"], "Overridden name");
+        assert_eq!(
+            comments["database"],
+            "Database settings\nused by the server"
+        );
+        assert_eq!(comments["servers.*.ip"], "Address of the server");
+        assert_eq!(comments["database.host"], "Indented");
+        assert!(!comments.contains_key("database.port"));
+        assert_eq!(comments.len(), 4);
+
+        // The root itself has no field, only the values below it are collected
+        let root = [PathSegment::Key("database".to_string())];
+        let comments = collect(&[base], &root);
+        assert_eq!(comments["host"], "Indented");
+        assert_eq!(comments.len(), 1);
+    }
+}
@@ -0,0 +1,1143 @@
+//! Procedural macro implementation of the
+//! [`static-toml`](https://docs.rs/static-toml) crate.
+//!
+//! This crate is not meant to be used directly.
+//! Depend on `static-toml` instead, which re-exports the macro alongside the
+//! types the generated code relies on.
+
+extern crate proc_macro;
+
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident as Ident2, Span as Span2, TokenStream as TokenStream2};
+use proc_macro_error::{diagnostic, proc_macro_error, Diagnostic, Level};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::LitStr;
+use toml::value::{Table, Value};
+
+use crate::location::Location;
+use crate::parse::{
+    CfgCondition, PathBase, StaticToml, StaticTomlItem, StorageClass, TomlPath, TomlSource
+};
+use crate::toml_tokens::{fixed_ident, key_path_string, path_string, PathSegment, TomlTokens};
+
+mod comments;
+mod location;
+mod parse;
+mod toml_tokens;
+mod user_types;
+
+// Documented at the re-export in `static-toml`.
+#[proc_macro_error]
+#[proc_macro]
+pub fn static_toml(input: TokenStream) -> TokenStream {
+    let token_stream2 = TokenStream2::from(input);
+    match static_toml2(token_stream2) {
+        Ok(ts) => ts.into(),
+        Err(errors) => {
+            // Report every error at once, `proc_macro_error` aborts afterwards.
+            for error in errors {
+                diagnostic(error).emit();
+            }
+            TokenStream::new()
+        }
+    }
+}
+
+/// Converts an error into the diagnostic reported to the user.
+fn diagnostic(error: Error) -> Diagnostic {
+    match error {
+        Error::Syn(e) => diagnostic!(e.span(), Level::Error, e.to_string()),
+        Error::MissingCargoManifestDirEnv => {
+            Diagnostic::new(Level::Error, "`CARGO_MANIFEST_DIR` env not set".to_string())
+                .help("use `cargo` to build".to_string())
+        }
+        Error::Located(e, location) => diagnostic(*e).note(location.to_string()),
+        Error::Toml(p, TomlError::FilePathInvalid) => diagnostic!(
+            p,
+            Level::Error,
+            "cannot construct valid file path";
+            note = "path to file must be valid utf-8"
+        ),
+        Error::Toml(p, TomlError::ReadToml(e)) => diagnostic!(p, Level::Error, e.to_string()),
+        Error::Toml(p, TomlError::ParseToml(e)) => diagnostic!(p, Level::Error, e.to_string()),
+        Error::Toml(p, TomlError::ParseInlineToml(e)) => {
+            diagnostic!(inline_error_span(&p, &e), Level::Error, e.to_string())
+        }
+        Error::Toml(p, TomlError::KeyInvalid(k, path)) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{k}` cannot be converted to a valid identifier");
+            help = format!(
+                "rename the key via `#[static_toml(rename({} = new_name))]` or set a \
+                 `key_fallback`",
+                key_path_string(&path)
+            )
+        ),
+        Error::Toml(p, TomlError::TypeOverrideInvalid { path, ty, found }) => diagnostic!(
+            p,
+            Level::Error,
+            format!(
+                "type `{ty}` cannot be used for `{}`, found {found}",
+                path_string(&path)
+            )
+        ),
+        Error::Toml(p, TomlError::ValueOutOfRange { path, value, ty }) => diagnostic!(
+            p,
+            Level::Error,
+            format!(
+                "value `{value}` of `{}` does not fit into `{ty}`",
+                path_string(&path)
+            )
+        ),
+        Error::Toml(p, TomlError::MapInvalid(path)) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{}` cannot be used as a map", path_string(&path));
+            note = "all values of a map must share the same data type"
+        ),
+        Error::Toml(
+            p,
+            TomlError::KeyCollision {
+                path,
+                first,
+                second,
+                ident
+            }
+        ) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{first}` and `{second}` both convert to `{ident}`");
+            help = format!(
+                "rename one of the keys via `#[static_toml(rename({} = new_name))]`",
+                key_path_string(&path)
+            )
+        ),
+        Error::Toml(p, TomlError::CfgBranchMismatch(first)) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{}` does not match the data types of `{first}`", p.value());
+            note = "all `cfg` branches must generate the same data types"
+        ),
+        Error::Toml(p, TomlError::BaseUnavailable(PathBase::OutDir)) => diagnostic!(
+            p,
+            Level::Error,
+            "`OUT_DIR` env not set";
+            help = "`OUT_DIR` is only set for crates with a build script"
+        ),
+        Error::Toml(p, TomlError::BaseUnavailable(PathBase::Env(var))) => {
+            diagnostic!(p, Level::Error, format!("`{var}` env not set"))
+        }
+        Error::Toml(p, TomlError::BaseUnavailable(_)) => diagnostic!(
+            p,
+            Level::Error,
+            "cannot determine the source file of the macro call";
+            note = "`source:` requires Rust 1.88 or newer"
+        ),
+        Error::Toml(p, TomlError::AtInvalid(reason)) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{}` cannot be used as root, {reason}", p.value())
+        ),
+        Error::Toml(p, TomlError::AtMissing(key, at)) => {
+            diagnostic!(p, Level::Error, format!("`{key}` of `{at}` does not exist"))
+        }
+        Error::Toml(p, TomlError::EnvVarMissing { path, name }) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{name}` env not set, used by `{}`", path_string(&path));
+            help = format!("set `{name}` or provide a default via `${{{name}:-default}}`")
+        ),
+        Error::Toml(p, TomlError::InterpolationInvalid { path, value }) => diagnostic!(
+            p,
+            Level::Error,
+            format!("`{value}` of `{}` cannot be interpolated", path_string(&path));
+            note = "variables are written as `${VAR}` or `${VAR:-default}`, `$${` is a literal `${`"
+        ),
+        Error::Toml(p, TomlError::SharedInvalid { path, name, reason }) => diagnostic!(
+            p,
+            Level::Error,
+            format!(
+                "`{}` cannot use the shared type `{name}`, {reason}",
+                path_string(&path)
+            )
+        ),
+        Error::Toml(p, TomlError::UserTypeInvalid { path, ty, reason }) => diagnostic!(
+            p,
+            Level::Error,
+            format!(
+                "`{}` cannot be embedded into `{ty}`, {reason}",
+                path_string(&path)
+            )
+        ),
+        Error::Toml(_, TomlError::Multiple(_)) => {
+            unreachable!("multiple errors are split before they are reported")
+        }
+    }
+}
+
+/// Process the input token stream and generate the corresponding Rust code
+/// using `proc_macro2`.
+///
+/// This function serves as the `proc_macro2` variant of the `static_toml`
+/// procedural macro.
+/// It is necessary for making the library testable.
+/// By using `proc_macro2` data structures, this function can be tested in
+/// environments where procedural macros are not natively supported.
+///
+/// Errors do not stop the generation, every item and value is processed to
+/// report all errors at once.
+fn static_toml2(input: TokenStream2) -> Result<TokenStream2, Vec<Error>> {
+    // Parse the input into StaticToml data structure.
+    let static_toml_data: StaticToml = syn::parse2(input).map_err(|e| vec![Error::Syn(e)])?;
+
+    // Iterate through each static_toml item, process it, and generate the
+    // corresponding Rust code.
+    let mut tokens = Vec::with_capacity(static_toml_data.0.len());
+    let mut errors = Vec::new();
+    for static_toml in static_toml_data.0.iter() {
+        match item_tokens(static_toml) {
+            Ok(item_tokens) => tokens.push(item_tokens),
+            Err(item_errors) => errors.extend(item_errors)
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(TokenStream2::from_iter(tokens)),
+        false => Err(errors)
+    }
+}
+
+/// Generates the Rust code for a single static_toml item.
+fn item_tokens(static_toml: &StaticTomlItem) -> Result<TokenStream2, Vec<Error>> {
+    // Errors in the generated code may originate from any file, they are
+    // reported at the first one.
+    let path = &static_toml.sources[0].paths[0].path;
+
+    // Read every `cfg` branch, all of them have to share the same types.
+    let mut errors = Vec::new();
+    let mut sources: Vec<LoadedSource> = Vec::with_capacity(static_toml.sources.len());
+    for source in static_toml.sources.iter() {
+        match read_source(source) {
+            Ok(loaded) => sources.push(loaded),
+            Err(e) => errors.extend(e)
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Select the subtree of `at` as the root of every branch.
+    if let Some(at) = &static_toml.at {
+        for loaded in sources.iter_mut() {
+            select_subtree(loaded, at)?;
+        }
+    }
+
+    // Remove skipped values, patterns that match in none of the branches are
+    // warned about.
+    let mut matched = vec![false; static_toml.attrs.skips.len()];
+    for loaded in sources.iter_mut() {
+        toml_tokens::skip_values(
+            &mut loaded.value,
+            &mut Vec::new(),
+            &static_toml.attrs,
+            &mut matched
+        );
+    }
+    // Interpolate environment variables, every variable read is tracked to
+    // rebuild when it changes.
+    let mut env_vars = BTreeSet::new();
+    if static_toml.attrs.interpolate.is_some() {
+        for (source, loaded) in static_toml.sources.iter().zip(sources.iter_mut()) {
+            if let Err(e) =
+                toml_tokens::interpolate_values(&mut loaded.value, &mut Vec::new(), &mut env_vars)
+            {
+                errors.extend(located_errors(&source.paths[0].path, e, loaded));
+            }
+        }
+    }
+
+    let warnings: Vec<TokenStream2> = static_toml
+        .attrs
+        .skips
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(skip, _)| warning(skip.span, &format!("`{skip}` in `skip` matches no key")))
+        .collect();
+
+    let value_table = &sources[0].value;
+    for (source, loaded) in static_toml.sources.iter().zip(sources.iter()).skip(1) {
+        if !loaded.value.type_eq(value_table) {
+            errors.push(Error::Toml(
+                source.paths[0].path.clone(),
+                TomlError::CfgBranchMismatch(path.value())
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Determine the root module name, either specified by the user or the default
+    // based on the static value's name.
+    let root_mod = static_toml.attrs.root_mod.clone().unwrap_or(format_ident!(
+        "{}",
+        static_toml.name.to_string().to_case(Case::Snake)
+    ));
+
+    // Determine the visibility of the generated code, either specified by the user
+    // or default.
+    let visibility = static_toml
+        .visibility
+        .as_ref()
+        .map(|vis| vis.to_token_stream())
+        .unwrap_or_default();
+
+    // Document the generated code with the comments of the keys, unless
+    // automatic documentation is disabled.
+    let mut attrs = static_toml.attrs.clone();
+    if attrs
+        .auto_doc
+        .as_ref()
+        .map(|lit_bool| lit_bool.value)
+        .unwrap_or(true)
+    {
+        let files: Vec<&str> = sources[0]
+            .files
+            .iter()
+            .map(|file| file.content.as_str())
+            .collect();
+        attrs.comments = comments::collect(&files, &sources[0].root);
+    }
+
+    // Existing types defined next to the macro call have their fields checked
+    // against the keys.
+    if !attrs.user_types.is_empty() {
+        let source = static_toml
+            .name
+            .span()
+            .local_file()
+            .and_then(|file| fs::read_to_string(file).ok());
+        attrs.user_type_fields = source
+            .as_deref()
+            .map(user_types::collect)
+            .unwrap_or_default();
+    }
+
+    // Generate the tokens for the types based on the parsed TOML data, their
+    // errors are reported together with the errors of the values below.
+    let (type_tokens, mut type_error) = match value_table.type_tokens(
+        root_mod.to_string().as_str(),
+        &mut Vec::new(),
+        &attrs,
+        visibility,
+        &static_toml.derive
+    ) {
+        Ok(type_tokens) => (type_tokens, None),
+        Err(e) => (TokenStream2::new(), Some(e))
+    };
+
+    let storage_class: &dyn ToTokens = match static_toml.storage_class {
+        StorageClass::Static(ref token) => token,
+        StorageClass::Const(ref token) => token
+    };
+
+    // Extract relevant fields from the StaticTomlItem.
+    let name = &static_toml.name;
+    let root_type = fixed_ident(
+        root_mod.to_string().as_str(),
+        &static_toml.attrs.prefix,
+        &static_toml.attrs.suffix
+    );
+
+    let StaticTomlItem {
+        doc,
+        other_attrs,
+        visibility,
+        ..
+    } = static_toml;
+
+    // Generate the static value for every `cfg` branch, a branch is only
+    // active if none of the previous ones are.
+    let mut tokens = Vec::with_capacity(sources.len() + 1);
+    let mut previous_predicates = Vec::new();
+    let mut include_file_paths = Vec::new();
+    for (source, loaded) in static_toml.sources.iter().zip(sources.iter()) {
+        let cfg = match &source.cfg {
+            None => quote!(),
+            Some(CfgCondition::Predicate(predicate)) if previous_predicates.is_empty() => {
+                quote!(#[cfg(#predicate)])
+            }
+            Some(CfgCondition::Predicate(predicate)) => {
+                quote!(#[cfg(all(#predicate, not(any(#(#previous_predicates),*))))])
+            }
+            Some(CfgCondition::Fallback) => {
+                quote!(#[cfg(not(any(#(#previous_predicates),*)))])
+            }
+        };
+        if let Some(CfgCondition::Predicate(predicate)) = &source.cfg {
+            previous_predicates.push(predicate);
+        }
+
+        // Generate the tokens for the static value based on the parsed TOML data.
+        let mut namespace = vec![root_mod.clone()];
+        let static_tokens = loaded.value.static_tokens(
+            root_mod.to_string().as_str(),
+            &mut Vec::new(),
+            &attrs,
+            &mut namespace
+        );
+
+        // The types are generated from the first branch, so their errors are
+        // combined with the errors of its values.
+        // Both check the keys, errors found by both are only reported once.
+        let static_tokens = match (type_error.take(), static_tokens) {
+            (None, Ok(static_tokens)) => static_tokens,
+            (type_error, static_tokens) => {
+                let type_errors = type_error.map(TomlError::into_errors).unwrap_or_default();
+                let reported: Vec<String> = type_errors.iter().map(|e| format!("{e:?}")).collect();
+                let value_errors = static_tokens
+                    .err()
+                    .map(TomlError::into_errors)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|e| !reported.contains(&format!("{e:?}")));
+                let e = TomlError::Multiple(type_errors.into_iter().chain(value_errors).collect());
+                errors.extend(located_errors(&source.paths[0].path, e, loaded));
+                continue;
+            }
+        };
+
+        // Generate `Default` implementations returning the embedded values.
+        let default_impls = match static_toml.attrs.impl_default {
+            None => Vec::new(),
+            Some(_) => match toml_tokens::default_impls(
+                &loaded.value,
+                root_mod.to_string().as_str(),
+                &mut Vec::new(),
+                &static_toml.attrs,
+                &mut vec![root_mod.clone()]
+            ) {
+                Ok(default_impls) => default_impls,
+                Err(e) => {
+                    errors.extend(located_errors(&source.paths[0].path, e, loaded));
+                    continue;
+                }
+            }
+        };
+
+        // Generate the function to load runtime overrides.
+        let load_tokens = toml_tokens::runtime_tokens::load(
+            quote!(#root_mod::#root_type),
+            &static_tokens,
+            &static_toml.attrs
+        );
+        let load_tokens = match load_tokens.is_empty() {
+            true => load_tokens,
+            false => quote!(#cfg #load_tokens)
+        };
+
+        // Generate auto doc comments.
+        let auto_doc = match (
+            static_toml
+                .attrs
+                .auto_doc
+                .as_ref()
+                .map(|lit_bool| lit_bool.value),
+            static_toml.doc.len()
+        ) {
+            (None, 0) | (Some(true), _) => {
+                let files: Vec<(String, &str)> = loaded
+                    .files
+                    .iter()
+                    .map(|file| (file.label(), file.content.as_str()))
+                    .collect();
+                toml_tokens::gen_auto_doc(&files, &static_toml.storage_class)
+            }
+
+            (None, _) | (Some(false), _) => Default::default()
+        };
+
+        // Fields may be deprecated via `attr`, constructing the value is not
+        // a use of them.
+        tokens.push(quote! {
+            #(#doc)*
+            #auto_doc
+            #cfg
+            #[allow(deprecated)]
+            #visibility #storage_class #name: #root_mod::#root_type = #static_tokens;
+            #(#cfg #default_impls)*
+            #load_tokens
+        });
+        include_file_paths.extend(loaded.include_file_paths.iter());
+    }
+
+    // Generate the final Rust code for the types.
+    tokens.push(quote! {
+        #(#other_attrs)*
+        #type_tokens
+
+        // This is a trick to make the compiler re-evaluate the macro call when the included files change.
+        #(const _: &str = include_str!(#include_file_paths);)*
+        #(const _: std::option::Option<&str> = option_env!(#env_vars);)*
+
+        #(#warnings)*
+    });
+
+    match errors.is_empty() {
+        true => Ok(TokenStream2::from_iter(tokens)),
+        false => Err(errors)
+    }
+}
+
+/// Generates a warning at the span.
+///
+/// `proc_macro_error` only emits warnings on nightly, the use of a deprecated
+/// item is warned about on stable as well.
+fn warning(span: Span2, message: &str) -> TokenStream2 {
+    let ident = Ident2::new("StaticTomlWarning", span);
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct StaticTomlWarning;
+            let _ = #ident;
+        };
+    }
+}
+
+/// A TOML document read from the files of a [`TomlSource`].
+struct LoadedSource {
+    /// The merged TOML document.
+    value: Value,
+    /// Every file of the source, used for the auto doc and diagnostics.
+    files: Vec<LoadedFile>,
+    /// Full paths of the files for `include_str!`.
+    include_file_paths: Vec<String>,
+    /// Path to the value used as root, see [`select_subtree`].
+    root: Vec<PathSegment>
+}
+
+/// A single file of a [`LoadedSource`].
+struct LoadedFile {
+    /// The path as written in the macro call.
+    name: String,
+    content: String,
+    inline: bool
+}
+
+impl LoadedFile {
+    /// The label of the file in the auto doc.
+    fn label(&self) -> String {
+        match self.inline {
+            true => "inline TOML".to_string(),
+            false => format!("`{}`", self.name)
+        }
+    }
+
+    /// The name of the file in locations, inline TOML has no name of its own.
+    fn location_name(&self) -> &str {
+        match self.inline {
+            true => "inline TOML",
+            false => &self.name
+        }
+    }
+}
+
+/// Selects the subtree at the dotted key of `at = ".."` as the root of the
+/// source.
+///
+/// Arrays may be indexed by integer keys, like `servers.0`.
+/// The subtree must be a table or an array.
+fn select_subtree(source: &mut LoadedSource, at: &LitStr) -> Result<(), Vec<Error>> {
+    let error = |e| vec![Error::Toml(at.clone(), e)];
+    let keys = toml_edit::Key::parse(&at.value())
+        .map_err(|e| error(TomlError::AtInvalid(e.message().to_string())))?;
+
+    let mut value = std::mem::replace(&mut source.value, Value::Boolean(false));
+    for key in keys {
+        let key = key.get();
+        let next = match &mut value {
+            Value::Table(table) => {
+                source.root.push(PathSegment::Key(key.to_string()));
+                table.remove(key)
+            }
+            Value::Array(array) => {
+                let index = key.parse().ok().filter(|i| *i < array.len());
+                source
+                    .root
+                    .push(PathSegment::Index(index.unwrap_or_default()));
+                index.map(|i| array.swap_remove(i))
+            }
+            _ => None
+        };
+        value = next.ok_or_else(|| error(TomlError::AtMissing(key.to_string(), at.value())))?;
+    }
+
+    match value {
+        Value::Table(_) | Value::Array(_) => {
+            source.value = value;
+            Ok(())
+        }
+        value => Err(error(TomlError::AtInvalid(format!(
+            "found {}, expected a table or an array",
+            value.type_str()
+        ))))
+    }
+}
+
+/// Splits an error of the code generation into the errors to report.
+///
+/// Every error is located at its offending value, if it can be found in the
+/// files.
+fn located_errors(lit: &LitStr, error: TomlError, source: &LoadedSource) -> Vec<Error> {
+    let files: Vec<(&str, &str)> = source
+        .files
+        .iter()
+        .map(|file| (file.location_name(), file.content.as_str()))
+        .collect();
+
+    error
+        .into_errors()
+        .into_iter()
+        .map(|error| {
+            let location = error.path().and_then(|path| {
+                // Paths start at the root, which may be a subtree of the files.
+                let path = [source.root.as_slice(), path].concat();
+                Location::find(&files, &path)
+            });
+            let error = Error::Toml(lit.clone(), error);
+            match location {
+                Some(location) => Error::Located(Box::new(error), location),
+                None => error
+            }
+        })
+        .collect()
+}
+
+/// Reads the files of a source and merges them into a single TOML document.
+fn read_source(source: &TomlSource) -> Result<LoadedSource, Vec<Error>> {
+    let mut table = Table::new();
+    let mut files = Vec::with_capacity(source.paths.len());
+    let mut include_file_paths = Vec::with_capacity(source.paths.len());
+    let mut errors = Vec::new();
+    for toml_path in source.paths.iter() {
+        // Parse every file and merge it into the previous ones.
+        match read_file(toml_path) {
+            Ok((file_table, file, include_file_path)) => {
+                merge_tables(&mut table, file_table);
+                files.push(file);
+                include_file_paths.extend(include_file_path);
+            }
+            Err(e) => errors.push(e)
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(LoadedSource {
+            value: Value::Table(table),
+            files,
+            include_file_paths,
+            root: Vec::new()
+        }),
+        false => Err(errors)
+    }
+}
+
+/// Reads and parses a single file of a source.
+///
+/// Returns the TOML table, the file and the full path of the file for
+/// `include_str!`, inline TOML has no such path.
+fn read_file(toml_path: &TomlPath) -> Result<(Table, LoadedFile, Option<String>), Error> {
+    let TomlPath { base, path } = toml_path;
+
+    // Inline TOML is parsed directly and not tracked as a file.
+    if *base == PathBase::Inline {
+        let content = path.value();
+        let file = LoadedFile {
+            name: path.value(),
+            content,
+            inline: true
+        };
+        let inline_table: Table = toml::from_str(&file.content).map_err(|e| {
+            // Spans inside of the literal only work on nightly, the location is
+            // reported on stable as well.
+            let location = e.span().map(|span| {
+                Location::from_offset(file.location_name(), &file.content, span.start, false)
+            });
+            let error = Error::Toml(path.clone(), TomlError::ParseInlineToml(e));
+            match location {
+                Some(location) => Error::Located(Box::new(error), location),
+                None => error
+            }
+        })?;
+        return Ok((inline_table, file, None));
+    }
+
+    // Construct the full path to the TOML file that needs to be embedded.
+    let mut file_path = resolve_base(base, path)?;
+    file_path.push(path.value());
+    let include_file_path = file_path
+        .to_str()
+        .ok_or(Error::Toml(path.clone(), TomlError::FilePathInvalid))?
+        .to_string();
+
+    // Read the TOML file and parse it into a TOML table.
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| Error::Toml(path.clone(), TomlError::ReadToml(e)))?;
+    let file_table: Table = toml::from_str(&content).map_err(|e| {
+        let location = e
+            .span()
+            .map(|span| Location::from_offset(&path.value(), &content, span.start, false));
+        let error = Error::Toml(path.clone(), TomlError::ParseToml(e));
+        match location {
+            Some(location) => Error::Located(Box::new(error), location),
+            None => error
+        }
+    })?;
+
+    let file = LoadedFile {
+        name: path.value(),
+        content,
+        inline: false
+    };
+    Ok((file_table, file, Some(include_file_path)))
+}
+
+/// Determines the span of a parse error inside of an inline TOML literal.
+///
+/// Pointing into a literal is only possible on nightly and for literals without
+/// escapes, otherwise the whole literal is used.
+fn inline_error_span(lit: &LitStr, error: &toml::de::Error) -> Span2 {
+    let literal = lit.token();
+    let repr = literal.to_string();
+    let Some(range) = error.span()
+    else {
+        return lit.span();
+    };
+
+    // Offset of the content in the literal, e.g. `r#"` for raw strings.
+    let (offset, raw) = match repr.find('"') {
+        Some(quote) => (quote + 1, repr.starts_with('r')),
+        None => return lit.span()
+    };
+    if !raw && repr.contains('\\') {
+        return lit.span();
+    }
+
+    literal
+        .subspan(range.start + offset..range.end.max(range.start + 1) + offset)
+        .unwrap_or_else(|| lit.span())
+}
+
+/// Resolves the directory a path is relative to.
+fn resolve_base(base: &PathBase, path: &LitStr) -> Result<PathBuf, Error> {
+    let unavailable = || Error::Toml(path.clone(), TomlError::BaseUnavailable(base.clone()));
+    match base {
+        PathBase::ManifestDir => env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .ok_or(Error::MissingCargoManifestDirEnv),
+        PathBase::OutDir => env::var_os("OUT_DIR")
+            .map(PathBuf::from)
+            .ok_or_else(unavailable),
+        PathBase::Env(var) => env::var_os(var).map(PathBuf::from).ok_or_else(unavailable),
+        PathBase::Inline => unreachable!("inline TOML has no base directory"),
+        PathBase::Source => {
+            // The source file may be relative to the working directory of the
+            // compiler, `include_str!` requires an absolute path though.
+            let file = path.span().local_file().ok_or_else(unavailable)?;
+            let file = std::path::absolute(file).map_err(|_| unavailable())?;
+            file.parent().map(PathBuf::from).ok_or_else(unavailable)
+        }
+    }
+}
+
+/// Deep merges the `overlay` table into the `base` table.
+///
+/// Tables are merged recursively, every other value of the overlay replaces
+/// the value in the base, including arrays.
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge_tables(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+pub(crate) enum Error {
+    Syn(syn::Error),
+    MissingCargoManifestDirEnv,
+    Toml(LitStr, TomlError),
+    Located(Box<Error>, Location)
+}
+
+#[derive(Debug)]
+pub(crate) enum TomlError {
+    FilePathInvalid,
+    ReadToml(io::Error),
+    ParseToml(toml::de::Error),
+    ParseInlineToml(toml::de::Error),
+    KeyInvalid(String, Vec<PathSegment>),
+    TypeOverrideInvalid {
+        path: Vec<PathSegment>,
+        ty: String,
+        found: &'static str
+    },
+    ValueOutOfRange {
+        path: Vec<PathSegment>,
+        value: String,
+        ty: String
+    },
+    MapInvalid(Vec<PathSegment>),
+    KeyCollision {
+        path: Vec<PathSegment>,
+        first: String,
+        second: String,
+        ident: String
+    },
+    CfgBranchMismatch(String),
+    BaseUnavailable(PathBase),
+    AtInvalid(String),
+    AtMissing(String, String),
+    EnvVarMissing {
+        path: Vec<PathSegment>,
+        name: String
+    },
+    InterpolationInvalid {
+        path: Vec<PathSegment>,
+        value: String
+    },
+    SharedInvalid {
+        path: Vec<PathSegment>,
+        name: String,
+        reason: String
+    },
+    UserTypeInvalid {
+        path: Vec<PathSegment>,
+        ty: String,
+        reason: &'static str
+    },
+    Multiple(Vec<TomlError>)
+}
+
+impl TomlError {
+    /// Splits the error into every single error it contains.
+    fn into_errors(self) -> Vec<TomlError> {
+        match self {
+            TomlError::Multiple(errors) => errors,
+            error => vec![error]
+        }
+    }
+
+    /// The path to the offending value, if the error originates from a value.
+    fn path(&self) -> Option<&[PathSegment]> {
+        match self {
+            TomlError::KeyInvalid(_, path) |
+            TomlError::TypeOverrideInvalid { path, .. } |
+            TomlError::ValueOutOfRange { path, .. } |
+            TomlError::MapInvalid(path) |
+            TomlError::KeyCollision { path, .. } |
+            TomlError::EnvVarMissing { path, .. } |
+            TomlError::InterpolationInvalid { path, .. } |
+            TomlError::SharedInvalid { path, .. } |
+            TomlError::UserTypeInvalid { path, .. } => Some(path),
+            _ => None
+        }
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Syn(e) => write!(f, "Syn({:?})", e),
+            Error::MissingCargoManifestDirEnv => write!(f, "MissingCargoManifestDirEnv"),
+            Error::Toml(p, e) => write!(f, "Toml({}, {:?})", p.value(), e),
+            Error::Located(e, location) => write!(f, "Located({:?}, {})", e, location)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use proc_macro2::Span as Span2;
+    use syn::parse_quote;
+
+    use super::*;
+
+    /// Serializes the tests that change environment variables.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Creates a source of inline TOML documents, merged in order.
+    fn inline_source(documents: &[&str]) -> TomlSource {
+        TomlSource {
+            cfg: None,
+            paths: documents
+                .iter()
+                .map(|document| TomlPath {
+                    base: PathBase::Inline,
+                    path: LitStr::new(document, Span2::call_site())
+                })
+                .collect()
+        }
+    }
+
+    /// Writes the files into a directory in the temp dir and returns their
+    /// absolute paths, which are included as they are.
+    fn test_files(dir: &str, files: &[(&str, &str)]) -> Vec<LitStr> {
+        let dir = env::temp_dir().join(format!("static-toml-{dir}"));
+        fs::create_dir_all(&dir).unwrap();
+        files
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                fs::write(&path, content).unwrap();
+                LitStr::new(path.to_str().unwrap(), Span2::call_site())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_tables_works() {
+        let mut base: Table = toml::from_str(
+            r#"
+        name = "base"
+        ports = [8000, 8001]
+
+        [database]
+        host = "localhost"
+        port = 5432
+        "#
+        )
+        .unwrap();
+        let overlay: Table = toml::from_str(
+            r#"
+        name = "overlay"
+        ports = [9000]
+
+        [database]
+        port = 6543
+        "#
+        )
+        .unwrap();
+
+        // Later values win, arrays are replaced and tables merged
+        merge_tables(&mut base, overlay);
+        let expected: Table = toml::from_str(
+            r#"
+        name = "overlay"
+        ports = [9000]
+
+        [database]
+        host = "localhost"
+        port = 6543
+        "#
+        )
+        .unwrap();
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn include_str_per_file() {
+        let files = test_files("include-str", &[
+            (
+                "base.toml",
+                "name = \"base\"\n[database]\nhost = \"localhost\"\n"
+            ),
+            (
+                "overlay.toml",
+                "name = \"overlay\"\n[database]\nport = 6543\n"
+            )
+        ]);
+        let (base, overlay) = (&files[0], &files[1]);
+        let tokens = static_toml2(quote! {
+            static CONFIG = include_toml!(#base, #overlay);
+        })
+        .unwrap()
+        .to_string();
+        assert_eq!(tokens.matches("include_str !").count(), 2);
+        assert!(tokens.contains("\"overlay\""));
+        assert!(tokens.contains("\"localhost\""));
+        assert!(tokens.contains("6543"));
+    }
+
+    #[test]
+    fn invalid_files_are_reported() {
+        let files = test_files("invalid-files", &[
+            ("base.toml", "name = \"base\"\n"),
+            ("invalid.toml", "name = \"invalid\"\nport = \n")
+        ]);
+        let (base, invalid) = (&files[0], &files[1]);
+        let res = static_toml2(quote! {
+            static CONFIG = include_toml!(#base, #invalid);
+        });
+
+        // Parse errors name the file and the location they occur at
+        let Err(errors) = res
+        else {
+            panic!("invalid file was parsed");
+        };
+        let [Error::Located(error, location)] = errors.as_slice()
+        else {
+            panic!("unexpected errors {errors:?}");
+        };
+        let Error::Toml(path, TomlError::ParseToml(_)) = error.as_ref()
+        else {
+            panic!("unexpected error {error:?}");
+        };
+        assert_eq!(path.value(), invalid.value());
+        assert!(location
+            .to_string()
+            .starts_with(&format!("{}:2:", invalid.value())));
+    }
+
+    #[test]
+    fn type_and_value_errors_are_collected() {
+        let files = test_files("type-and-value-errors", &[(
+            "config.toml",
+            "\"1st\" = 1\nfooBar = 2\nfoo_bar = 3\nport = 8080\n"
+        )]);
+        let config = &files[0];
+        let res = static_toml2(quote! {
+            #[static_toml(int = u8)]
+            static CONFIG = include_toml!(#config);
+        });
+
+        let Err(errors) = res
+        else {
+            panic!("invalid file was accepted");
+        };
+        let errors: Vec<String> = errors
+            .into_iter()
+            .map(|e| match e {
+                Error::Located(e, _) => *e,
+                e => e
+            })
+            .map(|e| match e {
+                Error::Toml(_, TomlError::KeyInvalid(key, _)) => format!("invalid {key}"),
+                Error::Toml(_, TomlError::KeyCollision { first, second, .. }) => {
+                    format!("collision {first} {second}")
+                }
+                Error::Toml(_, TomlError::ValueOutOfRange { value, .. }) => {
+                    format!("out of range {value}")
+                }
+                e => panic!("unexpected error {e:?}")
+            })
+            .collect();
+        assert_eq!(errors, [
+            "invalid 1st",
+            "collision fooBar foo_bar",
+            "out of range 8080"
+        ]);
+    }
+
+    #[test]
+    fn inline_errors_are_located() {
+        let source = inline_source(&["name = \"inline\"\nport = \n"]);
+        let Err(errors) = read_source(&source)
+        else {
+            panic!("invalid inline TOML was parsed");
+        };
+        let [Error::Located(error, location)] = errors.as_slice()
+        else {
+            panic!("unexpected errors {errors:?}");
+        };
+        assert!(matches!(
+            error.as_ref(),
+            Error::Toml(_, TomlError::ParseInlineToml(_))
+        ));
+        assert!(location.to_string().starts_with("inline TOML:2:"));
+
+        // Values are located in inline TOML as well
+        let res = static_toml2(quote! {
+            #[static_toml(int = u8)]
+            static CONFIG = toml!("name = \"inline\"\nport = 8080");
+        });
+        let Err(errors) = res
+        else {
+            panic!("out of range value was accepted");
+        };
+        let [Error::Located(_, location)] = errors.as_slice()
+        else {
+            panic!("unexpected errors {errors:?}");
+        };
+        assert!(location.to_string().starts_with("inline TOML:2:1"));
+    }
+
+    #[test]
+    fn resolve_base_works() {
+        let _env = ENV_LOCK.lock().unwrap();
+        let path: LitStr = parse_quote!("config.toml");
+        let unavailable = |base: PathBase| match resolve_base(&base, &path) {
+            Err(Error::Toml(_, TomlError::BaseUnavailable(unavailable))) => unavailable == base,
+            _ => false
+        };
+
+        // Cargo sets the manifest dir for tests as well
+        assert_eq!(
+            resolve_base(&PathBase::ManifestDir, &path).unwrap(),
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        );
+
+        // `OUT_DIR` is restored afterwards in case the tests run with one
+        let out_dir = env::var_os("OUT_DIR");
+        env::remove_var("OUT_DIR");
+        assert!(unavailable(PathBase::OutDir));
+        env::set_var("OUT_DIR", "/tmp/out");
+        assert_eq!(
+            resolve_base(&PathBase::OutDir, &path).unwrap(),
+            PathBuf::from("/tmp/out")
+        );
+        match out_dir {
+            Some(out_dir) => env::set_var("OUT_DIR", out_dir),
+            None => env::remove_var("OUT_DIR")
+        }
+
+        env::set_var("STATIC_TOML_TEST_BASE", "/etc/app");
+        assert_eq!(
+            resolve_base(&PathBase::Env("STATIC_TOML_TEST_BASE".to_string()), &path).unwrap(),
+            PathBuf::from("/etc/app")
+        );
+        env::remove_var("STATIC_TOML_TEST_BASE");
+        assert!(unavailable(PathBase::Env(
+            "STATIC_TOML_TEST_BASE".to_string()
+        )));
+
+        // Outside of a macro call, spans have no source file
+        assert!(unavailable(PathBase::Source));
+    }
+
+    #[test]
+    fn comments_work_with_at() {
+        let source = inline_source(&["
+        [[servers]]
+        # Address of the first server
+        ip = \"10.0.0.1\"
+
+        [[servers]]
+        # Address of the second server
+        ip = \"10.0.0.2\"
+        "]);
+        let mut source = read_source(&source).unwrap();
+        select_subtree(&mut source, &parse_quote!("servers.1")).unwrap();
+
+        let files: Vec<&str> = source
+            .files
+            .iter()
+            .map(|file| file.content.as_str())
+            .collect();
+        let comments = comments::collect(&files, &source.root);
+        assert_eq!(comments["ip"], "Address of the second server");
+        assert_eq!(comments.len(), 1);
+    }
+}
@@ -0,0 +1,164 @@
+//! Locates values in TOML files for diagnostics.
+//!
+//! Errors found while generating code only know the path to the offending
+//! value. The `location` module resolves such paths to the line and column in
+//! the included files using the spans of `toml_edit`, so the diagnostics can
+//! point at the offending line.
+
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+use crate::toml_tokens::PathSegment;
+
+/// A line and column in a TOML file.
+#[derive(Debug)]
+pub(crate) struct Location {
+    file: String,
+    line: usize,
+    column: usize,
+    snippet: Option<String>
+}
+
+/// A TOML node that may contain other nodes.
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Item(&'a Item),
+    Value(&'a Value),
+    Table(&'a toml_edit::Table)
+}
+
+impl Location {
+    /// Creates the location of a byte offset in the content of a file.
+    ///
+    /// If `snippet` is set, the line is included in the location.
+    pub fn from_offset(file: &str, content: &str, offset: usize, snippet: bool) -> Self {
+        let offset = offset.min(content.len());
+        let line_start = content[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = content[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(content.len());
+
+        Location {
+            file: file.to_string(),
+            line: content[..offset].matches('\n').count() + 1,
+            column: content[line_start..offset].chars().count() + 1,
+            snippet: snippet.then(|| content[line_start..line_end].trim_end().to_string())
+        }
+    }
+
+    /// Finds the location of the value at the path.
+    ///
+    /// The files are searched in reverse order, as later files override the
+    /// values of earlier ones.
+    /// Keys are located at the key itself, array items at their value.
+    pub fn find(files: &[(&str, &str)], path: &[PathSegment]) -> Option<Self> {
+        files.iter().rev().find_map(|(file, content)| {
+            let document = ImDocument::parse(*content).ok()?;
+            let span = find_span(Node::Item(document.as_item()), path)?;
+            Some(Location::from_offset(file, content, span.start, true))
+        })
+    }
+}
+
+/// Finds the span of the value at the path, starting at the node.
+fn find_span(node: Node, path: &[PathSegment]) -> Option<Range<usize>> {
+    let (segment, rest) = path.split_first()?;
+    let (next, span) = match segment {
+        PathSegment::Key(key) | PathSegment::Entry(key) => {
+            let (key, item) = node.as_table_like()?.get_key_value(key)?;
+            (Node::Item(item), key.span().or_else(|| item.span()))
+        }
+        PathSegment::Index(i) | PathSegment::Item(i) | PathSegment::Variant(i) => match node {
+            Node::Item(Item::ArrayOfTables(tables)) => {
+                let table = tables.get(*i)?;
+                (Node::Table(table), table.span())
+            }
+            Node::Item(Item::Value(Value::Array(array))) | Node::Value(Value::Array(array)) => {
+                let value = array.get(*i)?;
+                (Node::Value(value), value.span())
+            }
+            _ => return None
+        }
+    };
+
+    match rest.is_empty() {
+        true => span,
+        false => find_span(next, rest)
+    }
+}
+
+impl<'a> Node<'a> {
+    fn as_table_like(self) -> Option<&'a dyn TableLike> {
+        match self {
+            Node::Item(item) => item.as_table_like(),
+            Node::Value(value) => value.as_inline_table().map(|t| t as &dyn TableLike),
+            Node::Table(table) => Some(table)
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)?;
+        if let Some(snippet) = &self.snippet {
+            let gutter = " ".repeat(self.line.to_string().len());
+            let marker = " ".repeat(self.column - 1);
+            write!(
+                f,
+                "\n{gutter} |\n{} | {snippet}\n{gutter} | {marker}^",
+                self.line
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_works() {
+        let base = "[database]\nports = [8000, 8001]\n";
+        let overlay = "[database]\n\n  ports = [\n  8000,\n  70000\n]\n";
+        let files = [("base.toml", base), ("overlay.toml", overlay)];
+        let path = [
+            PathSegment::Key("database".to_string()),
+            PathSegment::Key("ports".to_string()),
+            PathSegment::Item(1)
+        ];
+
+        let location = Location::find(&files, &path).unwrap();
+        assert_eq!(location.file, "overlay.toml");
+        assert_eq!(location.line, 5);
+        assert_eq!(location.column, 3);
+        assert_eq!(
+            location.to_string(),
+            "overlay.toml:5:3\n  |\n5 |   70000\n  |   ^"
+        );
+
+        let location = Location::find(&files[..1], &path[..2]).unwrap();
+        assert_eq!(
+            location.to_string(),
+            "base.toml:2:1\n  |\n2 | ports = [8000, 8001]\n  | ^"
+        );
+
+        assert!(Location::find(&files, &[PathSegment::Key("missing".to_string())]).is_none());
+    }
+
+    #[test]
+    fn find_works_for_arrays_of_tables() {
+        let content = "[[servers]]\nname = \"alpha\"\n\n[[servers]]\n\"1st\" = true\n";
+        let path = [
+            PathSegment::Key("servers".to_string()),
+            PathSegment::Item(1),
+            PathSegment::Key("1st".to_string())
+        ];
+
+        let location = Location::find(&[("servers.toml", content)], &path).unwrap();
+        assert_eq!((location.line, location.column), (5, 1));
+    }
+}
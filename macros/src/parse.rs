@@ -0,0 +1,1359 @@
+//! Provides functionality for parsing TOML configuration files into Rust data
+//! structures.
+//!
+//! The `parse` module is primarily concerned with reading TOML files and
+//! converting their contents into a structured format suitable for further
+//! processing. This acts as a foundation for generating Rust source code that
+//! represents the configuration specified in the TOML files.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use proc_macro2::{Span as Span2, TokenStream as TokenStream2};
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Error, Ident as Ident2, LitBool, LitFloat, LitInt, LitStr, Path, Token, Visibility
+};
+
+use crate::toml_tokens::PathSegment;
+
+/// Represents the input to the static_toml macro.
+///
+/// Contains a collection of `StaticTomlItem` structs which represent individual
+/// TOML files and the associated configurations and attributes.
+pub struct StaticToml(pub Vec<StaticTomlItem>);
+
+/// Represents a single TOML file and its associated configurations and
+/// attributes.
+pub struct StaticTomlItem {
+    /// Configuration attributes specific to static_toml macro.
+    pub attrs: StaticTomlAttributes,
+    /// Attributes other than doc and derive.
+    pub other_attrs: Vec<Attribute>,
+    /// Documentation attributes.
+    pub doc: Vec<Attribute>,
+    /// Derive attributes.
+    pub derive: Vec<Attribute>,
+    /// Visibility of the static value (e.g., `pub`, `pub(crate)`).
+    pub visibility: Option<Visibility>,
+    /// Storage class of the variable (`static` or `const`).
+    pub storage_class: StorageClass,
+    /// The name of the static value.
+    pub name: Ident2,
+    /// The TOML documents to include, one per `cfg` branch.
+    pub sources: Vec<TomlSource>,
+    /// `at = "a.b"`, the dotted key of the subtree used as the root.
+    pub at: Option<LitStr>
+}
+
+/// A TOML document included by `include_toml!`.
+pub struct TomlSource {
+    /// The `cfg` branch selecting this document, `None` if there are no
+    /// branches.
+    pub cfg: Option<CfgCondition>,
+    /// The paths to the TOML files, later files are merged into earlier ones.
+    pub paths: Vec<TomlPath>
+}
+
+/// Path to a TOML file, relative to a base directory.
+pub struct TomlPath {
+    /// The directory the path is relative to.
+    pub base: PathBase,
+    /// The path as written in the macro call.
+    pub path: LitStr
+}
+
+/// Base directory of a [`TomlPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathBase {
+    /// `CARGO_MANIFEST_DIR`, used if no base is given.
+    ManifestDir,
+    /// `out_dir:`, the `OUT_DIR` of the build script.
+    OutDir,
+    /// `env("VAR"):`, the directory in an environment variable.
+    Env(String),
+    /// `source:`, the directory of the source file calling the macro.
+    Source,
+    /// `toml!(..)` or a string literal, the literal is the TOML content
+    /// itself instead of a path.
+    Inline
+}
+
+/// Condition of a `cfg` branch in `include_toml!`.
+#[derive(Debug, Clone)]
+pub enum CfgCondition {
+    /// `cfg(predicate)`, holding the predicate.
+    Predicate(TokenStream2),
+    /// `_`, used if no other branch matches.
+    Fallback
+}
+
+/// Contains configuration attributes for the static_toml macro.
+#[derive(Default, Clone)]
+pub struct StaticTomlAttributes {
+    pub prefix: Option<Ident2>,
+    pub suffix: Option<Ident2>,
+    pub root_mod: Option<Ident2>,
+    pub values_ident: Option<Ident2>,
+    pub prefer_slices: Option<LitBool>,
+    pub auto_doc: Option<LitBool>,
+    pub cow: Option<()>,
+    pub mixed_arrays: Option<MixedArrays>,
+    pub unify_tables: Option<()>,
+    pub int: Option<Ident2>,
+    pub float: Option<Ident2>,
+    pub types: Vec<TypeOverride>,
+    pub maps: Vec<MapTable>,
+    pub renames: Vec<Rename>,
+    pub key_fallback: Option<KeyFallback>,
+    pub skips: Vec<KeyPath>,
+    pub serde: Option<()>,
+    pub impl_default: Option<()>,
+    pub interpolate: Option<()>,
+    pub shared: Vec<SharedType>,
+    pub layout: Option<Layout>,
+    pub user_types: Vec<UserType>,
+    pub path_attrs: Vec<PathAttributes>,
+    /// Comments of the keys by their [`comment_key`], not configured via an
+    /// attribute but collected from the TOML files.
+    ///
+    /// [`comment_key`]: crate::comments::comment_key
+    pub comments: HashMap<String, String>,
+    /// Fields of the existing types of `use_type` by the name of the type,
+    /// not configured via an attribute but collected from the source file.
+    pub user_type_fields: HashMap<String, Vec<String>>
+}
+
+/// Path to values in a TOML file, e.g. `database.ports` or `servers.*`.
+///
+/// Used by attributes that configure specific values.
+#[derive(Debug, Clone)]
+pub struct KeyPath {
+    pub segments: Vec<KeySegment>,
+    /// Span of the path in the attribute, used for warnings.
+    pub span: Span2
+}
+
+/// A single segment of a [`KeyPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySegment {
+    /// A key of a table.
+    Key(String),
+    /// An index of an array.
+    Index(usize),
+    /// `*`, matches any key or index.
+    Wildcard
+}
+
+/// Overrides the numeric type of the values at a path, e.g.
+/// `database.ports = [u16]`.
+#[derive(Debug, Clone)]
+pub struct TypeOverride {
+    pub path: KeyPath,
+    pub ty: TypeSpec
+}
+
+/// A table represented as a map, e.g. `servers` or `(phf) = servers`.
+#[derive(Debug, Clone)]
+pub struct MapTable {
+    pub path: KeyPath,
+    pub backend: MapBackend
+}
+
+/// Storage of the entries of a [`MapTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapBackend {
+    /// A slice sorted by key, looked up via binary search.
+    Sorted,
+    /// A `phf::Map` using a perfect hash function.
+    #[cfg(feature = "phf")]
+    Phf
+}
+
+/// Renames the key at a path, e.g. `"1st" = first`.
+#[derive(Debug, Clone)]
+pub struct Rename {
+    pub path: KeyPath,
+    pub name: Ident2
+}
+
+/// A data type shared by all tables at a path, e.g. `servers.* = Server`.
+#[derive(Debug, Clone)]
+pub struct SharedType {
+    pub path: KeyPath,
+    pub name: Ident2
+}
+
+/// An existing type used for all tables at a path instead of a generated
+/// one, e.g. `servers.* = crate::ServerConfig`.
+#[derive(Clone)]
+pub struct UserType {
+    pub path: KeyPath,
+    pub ty: syn::Path
+}
+
+/// Attributes for the values at a path, e.g. `database = #[derive(Hash)]`.
+#[derive(Clone)]
+pub struct PathAttributes {
+    pub path: KeyPath,
+    pub attrs: Vec<Attribute>
+}
+
+/// Handling of keys that cannot be converted into identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyFallback {
+    /// Keys starting with a digit are prefixed by `_`, other invalid
+    /// characters are replaced by `_`.
+    Prefix,
+    /// Like [`KeyFallback::Prefix`], but non-ASCII characters are
+    /// transliterated to ASCII first.
+    Transliterate
+}
+
+/// The type of a [`TypeOverride`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSpec {
+    /// A numeric type like `u16` or `f32`.
+    Numeric(Ident2),
+    /// An array whose items have the inner type, written as `[u16]`.
+    Array(Box<TypeSpec>)
+}
+
+impl Display for TypeSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeSpec::Numeric(ident) => write!(f, "{ident}"),
+            TypeSpec::Array(inner) => write!(f, "[{inner}]")
+        }
+    }
+}
+
+/// Integer types that may be used for TOML integers.
+pub const INT_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"
+];
+
+/// Float types that may be used for TOML floats.
+pub const FLOAT_TYPES: &[&str] = &["f32", "f64"];
+
+/// Representation of arrays whose elements do not share a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MixedArrays {
+    /// A tuple struct with one field per element.
+    Tuple,
+    /// A slice of an enum with one variant per distinct element type.
+    Enum
+}
+
+/// Module layout of the generated data types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Every value gets a module containing its data type.
+    Nested,
+    /// Leaf values use their Rust types directly, only structs, tuple
+    /// structs, enums and maps get a module.
+    Flat
+}
+
+/// A token representing the 'include_toml' or 'toml' keyword.
+enum IncludeTomlToken {
+    /// `include_toml!`, including TOML files.
+    IncludeToml,
+    /// `toml!`, containing inline TOML.
+    Toml
+}
+
+/// Storage class for the literal value.
+pub enum StorageClass {
+    Static(Token![static]),
+    Const(Token![const])
+}
+
+/// Parse implementation for `StaticToml`.
+///
+/// Parses the input into a `StaticToml` struct which contains a vector of
+/// `StaticTomlItem` structs.
+impl Parse for StaticToml {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut items = vec![];
+
+        // Parse StaticTomlItems until the input stream is empty.
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+
+        Ok(Self(items))
+    }
+}
+
+/// Parse implementation for `StaticTomlItem`.
+///
+/// Parses the input into a `StaticTomlItem` struct which represents a single
+/// TOML file and its associated configurations and attributes.
+impl Parse for StaticTomlItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Parse attributes.
+        let all_attrs = match input.peek(Token![#]) {
+            false => None,
+            true => Some(input.call(Attribute::parse_outer)?)
+        };
+
+        let mut attrs = StaticTomlAttributes::default();
+        let mut other_attrs = Vec::new();
+        let mut doc = Vec::new();
+        let mut derive = Vec::new();
+
+        // Separate attributes into doc, derive, other attributes, and static_toml
+        // specific attributes.
+        if let Some(all_attrs) = all_attrs {
+            for attr in all_attrs {
+                if attr.path().is_ident("doc") {
+                    doc.push(attr);
+                    continue;
+                }
+
+                if attr.path().is_ident("derive") {
+                    derive.push(attr);
+                    continue;
+                }
+
+                if !attr.path().is_ident("static_toml") {
+                    other_attrs.push(attr);
+                    continue;
+                }
+
+                // Parse static_toml specific attributes.
+                attr.parse_nested_meta(|meta| {
+                    let Some(key) = meta.path.get_ident()
+                    else {
+                        return Ok(());
+                    };
+
+                    match key.to_string().as_str() {
+                        "prefix" => attrs.prefix = Some(meta.value()?.parse()?),
+                        "suffix" => attrs.suffix = Some(meta.value()?.parse()?),
+                        "root_mod" => attrs.root_mod = Some(meta.value()?.parse()?),
+                        "values_ident" => attrs.values_ident = Some(meta.value()?.parse()?),
+                        "prefer_slices" => attrs.prefer_slices = Some(meta.value()?.parse()?),
+                        "auto_doc" => attrs.auto_doc = Some(meta.value()?.parse()?),
+                        "cow" => attrs.cow = Some(Self::validate_no_value(&meta, "cow")?),
+                        "mixed_arrays" => attrs.mixed_arrays = Some(meta.value()?.parse()?),
+                        "unify_tables" => {
+                            attrs.unify_tables =
+                                Some(Self::validate_no_value(&meta, "unify_tables")?)
+                        }
+                        "int" => attrs.int = Some(Self::parse_numeric_type(&meta, INT_TYPES)?),
+                        "float" => {
+                            attrs.float = Some(Self::parse_numeric_type(&meta, FLOAT_TYPES)?)
+                        }
+                        "type" => {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let overrides =
+                                content.parse_terminated(TypeOverride::parse, Token![,])?;
+                            attrs.types.extend(overrides);
+                        }
+                        "map" => {
+                            let backend = match meta.input.peek(syn::token::Paren) {
+                                true => {
+                                    let content;
+                                    syn::parenthesized!(content in meta.input);
+                                    content.parse()?
+                                }
+                                false => MapBackend::Sorted
+                            };
+                            let path = meta.value()?.parse()?;
+                            attrs.maps.push(MapTable { path, backend });
+                        }
+                        "rename" => {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let renames = content.parse_terminated(Rename::parse, Token![,])?;
+                            attrs.renames.extend(renames);
+                        }
+                        "shared" => {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let shared = content.parse_terminated(SharedType::parse, Token![,])?;
+                            attrs.shared.extend(shared);
+                        }
+                        "use_type" => {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let user_types =
+                                content.parse_terminated(UserType::parse, Token![,])?;
+                            attrs.user_types.extend(user_types);
+                        }
+                        "attr" => {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let path_attrs =
+                                content.parse_terminated(PathAttributes::parse, Token![,])?;
+                            attrs.path_attrs.extend(path_attrs);
+                        }
+                        "layout" => attrs.layout = Some(meta.value()?.parse()?),
+                        "key_fallback" => attrs.key_fallback = Some(meta.value()?.parse()?),
+                        "serde" => attrs.serde = Some(Self::validate_no_value(&meta, "serde")?),
+                        "impl_default" => {
+                            attrs.impl_default =
+                                Some(Self::validate_no_value(&meta, "impl_default")?)
+                        }
+                        "interpolate" => {
+                            attrs.interpolate = Some(Self::validate_no_value(&meta, "interpolate")?)
+                        }
+                        "skip" => {
+                            let content;
+                            syn::parenthesized!(content in meta.input);
+                            let skips = content.parse_terminated(KeyPath::parse, Token![,])?;
+                            attrs.skips.extend(skips);
+                        }
+                        _ => {
+                            return Err(meta.error(
+                                "unexpected attribute, expected one of `prefix`, `suffix`, \
+                                 `root_mod`, `values_ident`, `prefer_slices`, `auto_doc`, `cow`, \
+                                 `mixed_arrays`, `unify_tables`, `int`, `float`, `type`, `map`, \
+                                 `rename`, `key_fallback`, `skip`, `serde`, `impl_default`, \
+                                 `interpolate`, `shared`, `layout`, `use_type` or `attr`"
+                            ))
+                        }
+                    }
+
+                    Ok(())
+                })?;
+            }
+        }
+
+        // Deriving serde traits requires the original keys.
+        if derive.iter().any(Self::is_serde_derive) {
+            attrs.serde = Some(());
+        }
+
+        // Parse visibility.
+        let visibility = match input.peek(Token![pub]) {
+            false => None,
+            true => Some(input.parse()?)
+        };
+
+        // Parse the remainder of the StaticTomlItem.
+        let storage_class = input.parse()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let (sources, at) = match input.peek(LitStr) {
+            true => (Self::parse_inline(input)?, None),
+            false => {
+                let token: IncludeTomlToken = input.parse()?;
+                input.parse::<Token![!]>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                match token {
+                    IncludeTomlToken::IncludeToml => {
+                        let sources = Self::parse_sources(&content)?;
+                        (sources, Self::parse_at(&content)?)
+                    }
+                    IncludeTomlToken::Toml => (Self::parse_inline(&content)?, None)
+                }
+            }
+        };
+        input.parse::<Token![;]>()?;
+
+        Ok(Self {
+            attrs,
+            other_attrs,
+            doc,
+            derive,
+            visibility,
+            storage_class,
+            name,
+            sources,
+            at
+        })
+    }
+}
+
+impl StaticTomlItem {
+    /// Parse inline TOML, a single string literal.
+    fn parse_inline(input: ParseStream) -> syn::Result<Vec<TomlSource>> {
+        let path = TomlPath {
+            base: PathBase::Inline,
+            path: input.parse()?
+        };
+        Ok(vec![TomlSource {
+            cfg: None,
+            paths: vec![path]
+        }])
+    }
+
+    /// Parse the content of `include_toml!`.
+    ///
+    /// This is either a list of paths, or a list of `cfg` branches like
+    /// `cfg(feature = "eu") => "eu.toml", _ => "default.toml"`.
+    /// Branches may layer multiple files via `cfg(..) => ("a.toml", "b.toml")`.
+    fn parse_sources(input: ParseStream) -> syn::Result<Vec<TomlSource>> {
+        let paths_error =
+            |input: ParseStream| input.error("expected at least one path to a TOML file");
+
+        let is_branch = |input: ParseStream| {
+            input.peek(Token![_]) ||
+                (input.peek2(syn::token::Paren) &&
+                    input
+                        .fork()
+                        .parse::<Ident2>()
+                        .is_ok_and(|ident| ident == "cfg"))
+        };
+
+        if !input.is_empty() && !is_branch(input) {
+            let mut paths = Vec::new();
+            while !input.is_empty() && !Self::peek_at(input) {
+                paths.push(input.parse()?);
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+            }
+            if paths.is_empty() {
+                return Err(paths_error(input));
+            }
+            return Ok(vec![TomlSource { cfg: None, paths }]);
+        }
+
+        let mut sources = Vec::new();
+        while !input.is_empty() && !Self::peek_at(input) {
+            if let Some(TomlSource {
+                cfg: Some(CfgCondition::Fallback),
+                ..
+            }) = sources.last()
+            {
+                return Err(input.error("the `_` branch must be the last one"));
+            }
+
+            let cfg = match input.peek(Token![_]) {
+                true => {
+                    input.parse::<Token![_]>()?;
+                    CfgCondition::Fallback
+                }
+                false => {
+                    let ident: Ident2 = input.parse()?;
+                    if ident != "cfg" {
+                        return Err(Error::new_spanned(
+                            ident,
+                            "expected a path, `cfg(..)` or `_`"
+                        ));
+                    }
+                    let predicate;
+                    syn::parenthesized!(predicate in input);
+                    CfgCondition::Predicate(predicate.parse()?)
+                }
+            };
+            input.parse::<Token![=>]>()?;
+
+            let paths: Vec<TomlPath> = match input.peek(syn::token::Paren) {
+                true => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let paths = content.parse_terminated(TomlPath::parse, Token![,])?;
+                    if paths.is_empty() {
+                        return Err(paths_error(&content));
+                    }
+                    paths.into_iter().collect()
+                }
+                false => vec![input.parse()?]
+            };
+            sources.push(TomlSource {
+                cfg: Some(cfg),
+                paths
+            });
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        if sources.is_empty() {
+            return Err(paths_error(input));
+        }
+
+        Ok(sources)
+    }
+
+    /// Checks whether a derive attribute derives `Serialize` or `Deserialize`.
+    fn is_serde_derive(attr: &Attribute) -> bool {
+        attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+            .is_ok_and(|paths| {
+                paths.iter().any(|path| {
+                    path.segments
+                        .last()
+                        .is_some_and(|s| s.ident == "Serialize" || s.ident == "Deserialize")
+                })
+            })
+    }
+
+    /// Checks whether the input continues with `at = "a.b"`.
+    fn peek_at(input: ParseStream) -> bool {
+        input.peek2(Token![=]) &&
+            input
+                .fork()
+                .parse::<Ident2>()
+                .is_ok_and(|ident| ident == "at")
+    }
+
+    /// Parse the optional `at = "a.b"` at the end of `include_toml!`.
+    fn parse_at(input: ParseStream) -> syn::Result<Option<LitStr>> {
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let ident: Ident2 = input.parse()?;
+        if ident != "at" {
+            return Err(Error::new_spanned(ident, "expected `at = \"..\"`"));
+        }
+        input.parse::<Token![=]>()?;
+        let at = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Some(at))
+    }
+
+    /// Validate that the `value()` part of [`ParseNestedMeta`] is empty.
+    ///
+    /// This is used to validate that static toml items which do not accept
+    /// values got no value.
+    fn validate_no_value(meta: &ParseNestedMeta, item: &str) -> syn::Result<()> {
+        let value = meta.value();
+        if let Ok(value) = value {
+            return Err(value.error(format!(
+                "`{item}` is a standalone attribute and does not accept a value"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Parse the value of an attribute expecting one of the given numeric
+    /// types.
+    fn parse_numeric_type(meta: &ParseNestedMeta, allowed: &[&str]) -> syn::Result<Ident2> {
+        let ident: Ident2 = meta.value()?.parse()?;
+        if !allowed.iter().any(|ty| ident == ty) {
+            return Err(Error::new_spanned(
+                &ident,
+                format!("expected one of `{}`", allowed.join("`, `"))
+            ));
+        }
+
+        Ok(ident)
+    }
+}
+
+impl KeyPath {
+    /// Checks whether the path of a value is matched by this path.
+    ///
+    /// Indices only match items of tuples, items of slices and entries of maps
+    /// share their type and are therefore only matched by wildcards.
+    pub fn matches(&self, path: &[PathSegment]) -> bool {
+        self.segments.len() == path.len() &&
+            self.segments
+                .iter()
+                .zip(path)
+                .all(|(pattern, segment)| match (pattern, segment) {
+                    (KeySegment::Wildcard, _) => true,
+                    (KeySegment::Key(a), PathSegment::Key(b)) => a == b,
+                    (KeySegment::Index(a), PathSegment::Index(b)) => a == b,
+                    _ => false
+                })
+    }
+}
+
+/// Parse implementation for `KeyPath`.
+///
+/// Parses dot separated keys, like `database.ports`.
+/// Keys may be identifiers (including keywords), string literals for keys
+/// that are not valid identifiers, integers for array indices or `*` as a
+/// wildcard.
+impl Parse for KeyPath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let start = input.span();
+        let mut end;
+        let mut segments = Vec::new();
+        loop {
+            end = input.span();
+            let lookahead = input.lookahead1();
+            if lookahead.peek(Token![*]) {
+                input.parse::<Token![*]>()?;
+                segments.push(KeySegment::Wildcard);
+            }
+            else if lookahead.peek(LitStr) {
+                segments.push(KeySegment::Key(input.parse::<LitStr>()?.value()));
+            }
+            else if lookahead.peek(LitInt) {
+                segments.push(KeySegment::Index(input.parse::<LitInt>()?.base10_parse()?));
+            }
+            else if lookahead.peek(LitFloat) {
+                // `0.1` in `data.0.1` is lexed as a float.
+                let float: LitFloat = input.parse()?;
+                for index in float.base10_digits().split('.') {
+                    let index = index
+                        .parse()
+                        .map_err(|_| Error::new_spanned(&float, "expected array indices"))?;
+                    segments.push(KeySegment::Index(index));
+                }
+            }
+            else if lookahead.peek(Ident2::peek_any) {
+                let ident = input.call(Ident2::parse_any)?.unraw();
+                segments.push(KeySegment::Key(ident.to_string()));
+            }
+            else {
+                return Err(lookahead.error());
+            }
+
+            if !input.peek(Token![.]) {
+                break;
+            }
+            input.parse::<Token![.]>()?;
+        }
+
+        // Joining spans is only possible on nightly.
+        let span = start.join(end).unwrap_or(start);
+        Ok(KeyPath { segments, span })
+    }
+}
+
+impl Display for KeyPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            match segment {
+                KeySegment::Key(key) if syn::parse_str::<Ident2>(key).is_ok() => {
+                    write!(f, "{key}")?
+                }
+                KeySegment::Key(key) => write!(f, "{key:?}")?,
+                KeySegment::Index(i) => write!(f, "{i}")?,
+                KeySegment::Wildcard => write!(f, "*")?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse implementation for `TypeOverride`.
+///
+/// Parses `path = type`, like `database.ports = [u16]`.
+impl Parse for TypeOverride {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse()?;
+        Ok(TypeOverride { path, ty })
+    }
+}
+
+/// Parse implementation for `Rename`.
+///
+/// Parses `path = name`, like `"1st" = first`.
+/// Keywords are allowed as names, they become raw identifiers.
+impl Parse for Rename {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let name = input.call(Ident2::parse_any)?.unraw();
+        Ok(Rename { path, name })
+    }
+}
+
+/// Parse implementation for `SharedType`.
+///
+/// Parses `path = Name`, like `servers.* = Server`.
+impl Parse for SharedType {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let name = input.parse()?;
+        Ok(SharedType { path, name })
+    }
+}
+
+/// Parse implementation for `UserType`.
+///
+/// Parses a path to the tables and the path of the type, like
+/// `servers.* = crate::ServerConfig`.
+impl Parse for UserType {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse()?;
+        Ok(UserType { path, ty })
+    }
+}
+
+/// Parse implementation for `PathAttributes`.
+///
+/// Parses a path followed by one or more outer attributes, like
+/// `owner.name = #[deprecated]`.
+impl Parse for PathAttributes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let attrs = Attribute::parse_outer(input)?;
+        if attrs.is_empty() {
+            return Err(input.error("expected an attribute like `#[derive(Hash)]`"));
+        }
+        Ok(PathAttributes { path, attrs })
+    }
+}
+
+/// Parse implementation for `MapBackend`.
+///
+/// Parses either `sorted` or `phf`, the latter requires the `phf` feature
+/// which provides the `phf` crate to the generated code.
+impl Parse for MapBackend {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident2 = input.parse()?;
+        match ident.to_string().as_str() {
+            "sorted" => Ok(MapBackend::Sorted),
+            #[cfg(feature = "phf")]
+            "phf" => Ok(MapBackend::Phf),
+            #[cfg(not(feature = "phf"))]
+            "phf" => Err(Error::new_spanned(
+                ident,
+                "`phf` maps require the `phf` feature of `static-toml`"
+            )),
+            _ => Err(Error::new_spanned(ident, "expected `sorted` or `phf`"))
+        }
+    }
+}
+
+/// Parse implementation for `KeyFallback`.
+///
+/// Parses either `prefix` or `transliterate`.
+impl Parse for KeyFallback {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident2 = input.parse()?;
+        match ident.to_string().as_str() {
+            "prefix" => Ok(KeyFallback::Prefix),
+            "transliterate" => Ok(KeyFallback::Transliterate),
+            _ => Err(Error::new_spanned(
+                ident,
+                "expected `prefix` or `transliterate`"
+            ))
+        }
+    }
+}
+
+/// Parse implementation for `TypeSpec`.
+///
+/// Parses numeric types, optionally nested in brackets for arrays.
+impl Parse for TypeSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            return Ok(TypeSpec::Array(Box::new(content.parse()?)));
+        }
+
+        let ident: Ident2 = input.parse()?;
+        let ty = ident.to_string();
+        if !INT_TYPES.contains(&ty.as_str()) && !FLOAT_TYPES.contains(&ty.as_str()) {
+            return Err(Error::new_spanned(
+                ident,
+                "expected a numeric type like `u16` or `f32`, or an array of one like `[u16]`"
+            ));
+        }
+
+        Ok(TypeSpec::Numeric(ident))
+    }
+}
+
+/// Parse implementation for `TomlPath`.
+///
+/// Parses a string literal, optionally prefixed by a base directory like
+/// `out_dir: "gen.toml"`, `env("CONFIG_DIR"): "config.toml"` or
+/// `source: "config.toml"`.
+impl Parse for TomlPath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(TomlPath {
+                base: PathBase::ManifestDir,
+                path: input.parse()?
+            });
+        }
+
+        let ident: Ident2 = input.parse()?;
+        let base = match ident.to_string().as_str() {
+            "out_dir" => PathBase::OutDir,
+            "source" => PathBase::Source,
+            "env" => {
+                let content;
+                syn::parenthesized!(content in input);
+                PathBase::Env(content.parse::<LitStr>()?.value())
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "expected a path, optionally prefixed by `out_dir:`, `env(\"VAR\"):` or \
+                     `source:`"
+                ))
+            }
+        };
+        input.parse::<Token![:]>()?;
+
+        Ok(TomlPath {
+            base,
+            path: input.parse()?
+        })
+    }
+}
+
+const EXPECTED_INCLUDE_TOML: &str = "expected `include_toml`, `toml` or a string literal";
+
+/// Parse implementation for `IncludeTomlToken`.
+///
+/// Ensures that the token is the 'include_toml' or 'toml' keyword.
+impl Parse for IncludeTomlToken {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Parse the token and ensure it matches 'include_toml' or 'toml'.
+        let include_toml: Ident2 = input
+            .parse()
+            .map_err(|e| syn::Error::new(e.span(), EXPECTED_INCLUDE_TOML))?;
+        match include_toml.to_string().as_str() {
+            "include_toml" => Ok(IncludeTomlToken::IncludeToml),
+            "toml" => Ok(IncludeTomlToken::Toml),
+            _ => Err(Error::new_spanned(include_toml, EXPECTED_INCLUDE_TOML))
+        }
+    }
+}
+
+/// Parse implementation for `MixedArrays`.
+///
+/// Parses either `tuple` or `enum`.
+impl Parse for MixedArrays {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `enum` is a keyword, so it has to be parsed via `parse_any`.
+        let ident = input.call(Ident2::parse_any)?;
+        match ident.to_string().as_str() {
+            "tuple" => Ok(MixedArrays::Tuple),
+            "enum" => Ok(MixedArrays::Enum),
+            _ => Err(Error::new_spanned(ident, "expected `tuple` or `enum`"))
+        }
+    }
+}
+
+/// Parse implementation for `Layout`.
+impl Parse for Layout {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident2 = input.parse()?;
+        match ident.to_string().as_str() {
+            "nested" => Ok(Layout::Nested),
+            "flat" => Ok(Layout::Flat),
+            _ => Err(Error::new_spanned(ident, "expected `nested` or `flat`"))
+        }
+    }
+}
+
+/// Parse implementation for `StorageClass`.
+///
+/// Parses the storage classes `static` or `const`.
+impl Parse for StorageClass {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![static]) {
+            return Ok(StorageClass::Static(input.parse::<Token![static]>()?));
+        }
+
+        if input.peek(Token![const]) {
+            return Ok(StorageClass::Const(input.parse::<Token![const]>()?));
+        }
+
+        Err(input.error("expected `static` or `const`"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span as Span2;
+    use quote::{format_ident, quote, ToTokens};
+    use syn::{parse_quote, Attribute, LitBool, Token, Visibility};
+
+    use crate::parse::{
+        CfgCondition, IncludeTomlToken, KeyFallback, KeySegment, Layout, MixedArrays, PathBase,
+        StaticToml, StaticTomlItem, StorageClass, EXPECTED_INCLUDE_TOML
+    };
+
+    impl StorageClass {
+        fn is_static(&self) -> bool {
+            match self {
+                StorageClass::Static(_) => true,
+                StorageClass::Const(_) => false
+            }
+        }
+
+        fn is_const(&self) -> bool {
+            match self {
+                StorageClass::Static(_) => false,
+                StorageClass::Const(_) => true
+            }
+        }
+    }
+
+    #[test]
+    fn parse_include_toml_token() {
+        let input = quote!(include_toml);
+        assert!(matches!(
+            syn::parse2::<IncludeTomlToken>(input),
+            Ok(IncludeTomlToken::IncludeToml)
+        ));
+
+        let input = quote!(toml);
+        assert!(matches!(
+            syn::parse2::<IncludeTomlToken>(input),
+            Ok(IncludeTomlToken::Toml)
+        ));
+
+        let input = quote!(include_json);
+        match syn::parse2::<IncludeTomlToken>(input) {
+            Err(e) => assert_eq!(e.to_string(), EXPECTED_INCLUDE_TOML),
+            Ok(_) => panic!("should be error variant")
+        }
+    }
+
+    #[test]
+    fn parse_static_toml() {
+        let items: StaticToml = parse_quote! {
+            #[static_toml(prefix = Cool, root_mod = img, unify_tables, map = sizes, impl_default)]
+            #[static_toml(interpolate, shared(servers.* = Server, *.*.address = Address))]
+            #[static_toml(int = u16, type(sizes.* = [u8], "odd key".r#type.0.1 = f32))]
+            static IMAGES = include_toml!("images.toml");
+
+            #[derive(PartialEq, Eq)]
+            #[derive(Default)]
+            #[static_toml(values_ident = items, suffix = Config, prefer_slices = false)]
+            #[static_toml(mixed_arrays = enum, key_fallback = transliterate, layout = flat)]
+            #[static_toml(use_type(servers.* = crate::ServerConfig, owner = Owner))]
+            #[static_toml(rename("1st" = first, servers.*.r#match = r#type))]
+            #[static_toml(skip(tool.other, "odd key".*))]
+            pub const CONFIG = include_toml!("config.toml", env("PRODUCT_DIR"): "product.toml",);
+
+            /// Documentation comment
+            #[must_use]
+            pub(crate) static EXAMPLE = include_toml!("example.toml");
+
+            static BASIC = include_toml!("basic.toml");
+        };
+
+        let mut items = items.0.into_iter();
+
+        let images = items.next().unwrap();
+        assert_eq!(images.attrs.prefix, Some(format_ident!("Cool")));
+        assert!(images.attrs.suffix.is_none());
+        assert_eq!(images.attrs.root_mod, Some(format_ident!("img")));
+        assert!(images.attrs.values_ident.is_none());
+        assert!(images.attrs.prefer_slices.is_none());
+        assert!(images.attrs.unify_tables.is_some());
+        assert_eq!(images.attrs.int, Some(format_ident!("u16")));
+        assert!(images.attrs.float.is_none());
+        assert_eq!(images.attrs.types.len(), 2);
+        assert_eq!(images.attrs.types[0].path.segments, [
+            KeySegment::Key("sizes".to_string()),
+            KeySegment::Wildcard
+        ]);
+        assert_eq!(images.attrs.types[0].ty.to_string(), "[u8]");
+        assert_eq!(images.attrs.types[1].path.segments, [
+            KeySegment::Key("odd key".to_string()),
+            KeySegment::Key("type".to_string()),
+            KeySegment::Index(0),
+            KeySegment::Index(1)
+        ]);
+        assert_eq!(images.attrs.types[1].ty.to_string(), "f32");
+        assert_eq!(images.attrs.maps.len(), 1);
+        assert_eq!(images.attrs.maps[0].path.segments, [KeySegment::Key(
+            "sizes".to_string()
+        )]);
+        assert!(images.attrs.impl_default.is_some());
+        assert!(images.attrs.interpolate.is_some());
+        assert_eq!(images.attrs.shared.len(), 2);
+        assert_eq!(images.attrs.shared[0].path.to_string(), "servers.*");
+        assert_eq!(images.attrs.shared[0].name, format_ident!("Server"));
+        assert_eq!(images.attrs.shared[1].path.to_string(), "*.*.address");
+        assert_eq!(images.attrs.shared[1].name, format_ident!("Address"));
+        assert!(images.other_attrs.is_empty());
+        assert!(images.derive.is_empty());
+        assert!(images.visibility.is_none());
+        assert!(images.storage_class.is_static());
+        assert_eq!(images.name, format_ident!("IMAGES"));
+        assert_eq!(
+            images.sources[0].paths[0].path.value().as_str(),
+            "images.toml"
+        );
+
+        let config = items.next().unwrap();
+        assert!(config.attrs.prefix.is_none());
+        assert_eq!(config.attrs.suffix, Some(format_ident!("Config")));
+        assert!(config.attrs.root_mod.is_none());
+        assert_eq!(config.attrs.values_ident, Some(format_ident!("items")));
+        assert_eq!(
+            config.attrs.prefer_slices,
+            Some(LitBool::new(false, Span2::call_site()))
+        );
+        assert_eq!(config.attrs.mixed_arrays, Some(MixedArrays::Enum));
+        assert_eq!(config.attrs.key_fallback, Some(KeyFallback::Transliterate));
+        assert_eq!(config.attrs.layout, Some(Layout::Flat));
+        assert_eq!(config.attrs.user_types.len(), 2);
+        assert_eq!(config.attrs.user_types[0].path.to_string(), "servers.*");
+        assert_eq!(
+            config.attrs.user_types[0].ty,
+            parse_quote!(crate::ServerConfig)
+        );
+        assert_eq!(config.attrs.user_types[1].path.to_string(), "owner");
+        assert_eq!(config.attrs.user_types[1].ty, parse_quote!(Owner));
+        assert_eq!(config.attrs.renames.len(), 2);
+        assert_eq!(config.attrs.renames[0].path.segments, [KeySegment::Key(
+            "1st".to_string()
+        )]);
+        assert_eq!(config.attrs.renames[0].name, format_ident!("first"));
+        assert_eq!(config.attrs.renames[1].path.segments, [
+            KeySegment::Key("servers".to_string()),
+            KeySegment::Wildcard,
+            KeySegment::Key("match".to_string())
+        ]);
+        assert_eq!(config.attrs.renames[1].name, format_ident!("type"));
+        assert_eq!(config.attrs.skips.len(), 2);
+        assert_eq!(config.attrs.skips[0].to_string(), "tool.other");
+        assert_eq!(config.attrs.skips[1].to_string(), "\"odd key\".*");
+        assert!(config.attrs.serde.is_none());
+        assert!(config.other_attrs.is_empty());
+        assert_eq!(
+            config.derive[0].to_token_stream().to_string(),
+            quote!(#[derive(PartialEq, Eq)]).to_string()
+        );
+        assert_eq!(
+            config.derive[1].to_token_stream().to_string(),
+            quote!(#[derive(Default)]).to_string()
+        );
+        assert_eq!(
+            config.visibility,
+            Some(Visibility::Public(Token![pub](Span2::call_site())))
+        );
+        assert!(config.storage_class.is_const());
+        assert_eq!(config.name, format_ident!("CONFIG"));
+        assert_eq!(
+            config.sources[0].paths[0].path.value().as_str(),
+            "config.toml"
+        );
+        assert_eq!(
+            config.sources[0].paths[1].path.value().as_str(),
+            "product.toml"
+        );
+        assert_eq!(config.sources[0].paths.len(), 2);
+        assert_eq!(
+            config.sources[0].paths[1].base,
+            PathBase::Env("PRODUCT_DIR".to_string())
+        );
+
+        let example = items.next().unwrap();
+        assert!(example.attrs.prefix.is_none());
+        assert!(example.attrs.suffix.is_none());
+        assert!(example.attrs.root_mod.is_none());
+        assert!(example.attrs.values_ident.is_none());
+        assert!(example.attrs.prefer_slices.is_none());
+        assert_eq!(
+            example.doc[0].path().get_ident(),
+            Some(&format_ident!("doc"))
+        );
+        assert_eq!(example.doc.len(), 1);
+        assert_eq!(
+            example.other_attrs[0].path().get_ident(),
+            Some(&format_ident!("must_use"))
+        );
+        assert_eq!(example.other_attrs.len(), 1);
+        let Some(Visibility::Restricted(_)) = example.visibility
+        else {
+            panic!("not a restricted visibility");
+        };
+        assert!(example.derive.is_empty());
+        assert!(example.storage_class.is_static());
+        assert_eq!(example.name, format_ident!("EXAMPLE"));
+        assert_eq!(
+            example.sources[0].paths[0].path.value().as_str(),
+            "example.toml"
+        );
+
+        let basic = items.next().unwrap();
+        assert!(basic.attrs.prefix.is_none());
+        assert!(basic.attrs.suffix.is_none());
+        assert!(basic.attrs.root_mod.is_none());
+        assert!(basic.attrs.values_ident.is_none());
+        assert!(basic.attrs.prefer_slices.is_none());
+        assert!(basic.attrs.mixed_arrays.is_none());
+        assert!(basic.attrs.unify_tables.is_none());
+        assert!(basic.attrs.int.is_none());
+        assert!(basic.attrs.types.is_empty());
+        assert!(basic.attrs.maps.is_empty());
+        assert!(basic.attrs.renames.is_empty());
+        assert!(basic.attrs.key_fallback.is_none());
+        assert!(basic.attrs.skips.is_empty());
+        assert!(basic.attrs.impl_default.is_none());
+        assert!(basic.attrs.interpolate.is_none());
+        assert!(basic.attrs.shared.is_empty());
+        assert!(basic.attrs.layout.is_none());
+        assert!(basic.attrs.user_types.is_empty());
+        assert!(basic.other_attrs.is_empty());
+        assert!(basic.visibility.is_none());
+        assert!(basic.derive.is_empty());
+        assert!(basic.storage_class.is_static());
+        assert_eq!(basic.name, format_ident!("BASIC"));
+        assert_eq!(
+            basic.sources[0].paths[0].path.value().as_str(),
+            "basic.toml"
+        );
+        assert_eq!(basic.sources[0].paths.len(), 1);
+        assert_eq!(basic.sources[0].paths[0].base, PathBase::ManifestDir);
+        assert!(basic.sources[0].cfg.is_none());
+    }
+
+    #[test]
+    fn parse_inline_toml() {
+        let items: StaticToml = parse_quote! {
+            static MACRO = toml!(r#"title = "inline""#);
+            static LITERAL = "title = 'inline'";
+        };
+
+        for item in items.0 {
+            assert_eq!(item.sources.len(), 1);
+            assert_eq!(item.sources[0].paths.len(), 1);
+            assert_eq!(item.sources[0].paths[0].base, PathBase::Inline);
+        }
+    }
+
+    #[test]
+    fn parse_cfg_branches() {
+        let item: StaticTomlItem = parse_quote! {
+            static CONFIG = include_toml!(
+                cfg(feature = "eu") => "eu.toml",
+                cfg(all(unix, feature = "us")) => ("us.toml", "unix.toml"),
+                _ => "default.toml"
+            );
+        };
+
+        assert_eq!(item.sources.len(), 3);
+        let Some(CfgCondition::Predicate(eu)) = &item.sources[0].cfg
+        else {
+            panic!("not a predicate");
+        };
+        assert_eq!(eu.to_string(), quote!(feature = "eu").to_string());
+        assert_eq!(item.sources[0].paths[0].path.value(), "eu.toml");
+        let Some(CfgCondition::Predicate(us)) = &item.sources[1].cfg
+        else {
+            panic!("not a predicate");
+        };
+        assert_eq!(
+            us.to_string(),
+            quote!(all(unix, feature = "us")).to_string()
+        );
+        assert_eq!(item.sources[1].paths.len(), 2);
+        assert!(matches!(item.sources[2].cfg, Some(CfgCondition::Fallback)));
+
+        let item: StaticTomlItem = parse_quote! {
+            static CONFIG = include_toml!(
+                cfg(unix) => (source: "base.toml", env("CONFIG_DIR"): "unix.toml"),
+                _ => out_dir: "gen.toml"
+            );
+        };
+        assert_eq!(item.sources[0].paths[0].base, PathBase::Source);
+        assert_eq!(
+            item.sources[0].paths[1].base,
+            PathBase::Env("CONFIG_DIR".to_string())
+        );
+        assert_eq!(item.sources[0].paths[1].path.value(), "unix.toml");
+        assert_eq!(item.sources[1].paths[0].base, PathBase::OutDir);
+
+        let res = syn::parse2::<StaticTomlItem>(quote! {
+            static CONFIG = include_toml!(_ => "default.toml", cfg(unix) => "unix.toml");
+        });
+        match res {
+            Err(e) => assert_eq!(e.to_string(), "the `_` branch must be the last one"),
+            Ok(_) => panic!("should be error variant")
+        }
+    }
+
+    #[test]
+    fn detect_serde_derive() {
+        let items: StaticToml = parse_quote! {
+            #[derive(Debug, serde::Deserialize)]
+            static DERIVED = include_toml!("config.toml");
+
+            #[derive(Serialize)]
+            static IMPORTED = include_toml!("config.toml");
+
+            #[static_toml(serde)]
+            static CONFIGURED = include_toml!("config.toml");
+
+            #[derive(Debug, Clone)]
+            static NONE = include_toml!("config.toml");
+        };
+
+        let serde: Vec<bool> = items
+            .0
+            .iter()
+            .map(|item| item.attrs.serde.is_some())
+            .collect();
+        assert_eq!(serde, [true, true, true, false]);
+    }
+
+    #[test]
+    fn parse_at() {
+        let item: StaticTomlItem = parse_quote! {
+            static MYAPP = include_toml!("Cargo.toml", at = "package.metadata.myapp");
+        };
+        assert_eq!(item.sources[0].paths.len(), 1);
+        assert_eq!(item.at.unwrap().value(), "package.metadata.myapp");
+
+        let item: StaticTomlItem = parse_quote! {
+            static CONFIG = include_toml!(
+                cfg(unix) => ("base.toml", "unix.toml"),
+                _ => "base.toml",
+                at = "config",
+            );
+        };
+        assert_eq!(item.sources.len(), 2);
+        assert_eq!(item.at.unwrap().value(), "config");
+
+        let item: StaticTomlItem = parse_quote! {
+            static CONFIG = include_toml!("config.toml");
+        };
+        assert!(item.at.is_none());
+
+        let res = syn::parse2::<StaticTomlItem>(quote! {
+            static CONFIG = include_toml!(at = "config");
+        });
+        match res {
+            Err(e) => assert_eq!(e.to_string(), "expected at least one path to a TOML file"),
+            Ok(_) => panic!("should be error variant")
+        }
+    }
+
+    #[test]
+    fn parse_path_attrs() {
+        let item: StaticTomlItem = parse_quote! {
+            #[static_toml(attr(database = #[derive(Hash)] #[doc(hidden)], owner.name = #[deprecated]))]
+            static CONFIG = include_toml!("config.toml");
+        };
+        assert_eq!(item.attrs.path_attrs.len(), 2);
+        assert_eq!(item.attrs.path_attrs[0].path.to_string(), "database");
+        assert_eq!(item.attrs.path_attrs[0].attrs, [
+            parse_quote!(#[derive(Hash)]),
+            parse_quote!(#[doc(hidden)])
+        ] as [Attribute; 2]);
+        assert_eq!(item.attrs.path_attrs[1].path.to_string(), "owner.name");
+        assert_eq!(
+            item.attrs.path_attrs[1].attrs,
+            [parse_quote!(#[deprecated])] as [Attribute; 1]
+        );
+        assert!(item.other_attrs.is_empty());
+
+        let item: StaticTomlItem = parse_quote! {
+            static CONFIG = include_toml!("config.toml");
+        };
+        assert!(item.attrs.path_attrs.is_empty());
+    }
+}
@@ -0,0 +1,1464 @@
+//! Converts TOML data into Rust tokens representing both type definitions and
+//! static data.
+//!
+//! The `toml_tokens` module handles the conversion of parsed TOML data into
+//! Rust tokens. These tokens can be used for generating Rust source code files
+//! that include both the type definitions and the static data based on the TOML
+//! structure.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use convert_case::{Case, Casing};
+use proc_macro2::{Literal, Span as Span2, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{Attribute, Ident as Ident2};
+use toml::value::{Array, Datetime, Offset};
+use toml::{Table, Value};
+
+use crate::comments::comment_key;
+use crate::parse::{
+    KeyFallback, Layout, MapBackend, MixedArrays, SharedType, StaticTomlAttributes, StorageClass,
+    TypeSpec, FLOAT_TYPES
+};
+
+pub(crate) mod runtime_tokens;
+mod static_tokens;
+mod type_tokens;
+
+#[cfg(test)]
+mod tests;
+
+/// Trait for generating Rust tokens based on TOML values.
+pub(crate) trait TomlTokens {
+    /// Compares the type of two TOML values.
+    ///
+    /// Returns `true` if both values have the same type.
+    fn type_eq(&self, other: &Self) -> bool;
+
+    /// Generates the Rust type definition tokens based on a TOML value.
+    ///
+    /// This method takes a TOML key, the path to the value, configuration,
+    /// visibility, and derive attributes and generates Rust type definitions.
+    fn type_tokens(
+        &self,
+        key: &str,
+        path: &mut Vec<PathSegment>,
+        config: &StaticTomlAttributes,
+        visibility: TokenStream2,
+        derive: &[Attribute]
+    ) -> Result<TokenStream2, super::TomlError>;
+
+    /// Generates the Rust static value tokens based on a TOML value.
+    ///
+    /// This method takes a TOML key, the path to the value, configuration, and
+    /// namespace and generates Rust static values.
+    fn static_tokens(
+        &self,
+        key: &str,
+        path: &mut Vec<PathSegment>,
+        config: &StaticTomlAttributes,
+        namespace: &mut Vec<Ident2>
+    ) -> Result<TokenStream2, super::TomlError>;
+}
+
+impl TomlTokens for Value {
+    fn type_eq(&self, other: &Self) -> bool {
+        use Value::*;
+
+        match (self, other) {
+            (String(_), String(_)) => true,
+            (Integer(_), Integer(_)) => true,
+            (Float(_), Float(_)) => true,
+            (Boolean(_), Boolean(_)) => true,
+            (Datetime(a), Datetime(b)) => {
+                (a.date.is_some(), a.time.is_some(), a.offset.is_some()) ==
+                    (b.date.is_some(), b.time.is_some(), b.offset.is_some())
+            }
+
+            (Array(a), Array(b)) => {
+                if a.len() != b.len() {
+                    return false;
+                }
+
+                a.iter()
+                    .zip(b.iter())
+                    .map(|(a, b)| a.type_eq(b))
+                    .reduce(|acc, b| acc && b)
+                    .unwrap_or(true)
+            }
+
+            (Table(a), Table(b)) => HashSet::<std::string::String>::from_iter(
+                a.keys().cloned().chain(b.keys().cloned())
+            )
+            .iter()
+            .map(|k| (a.get(k), b.get(k)))
+            .map(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => a.type_eq(b),
+                _ => false
+            })
+            .reduce(|acc, b| acc && b)
+            .unwrap_or(true),
+
+            _ => false
+        }
+    }
+
+    fn type_tokens(
+        &self,
+        key: &str,
+        path: &mut Vec<PathSegment>,
+        config: &StaticTomlAttributes,
+        visibility: TokenStream2,
+        derive: &[Attribute]
+    ) -> Result<TokenStream2, super::TomlError> {
+        use Value::*;
+
+        let mod_ident = snake_ident(key);
+        let type_ident = fixed_ident(key, &config.prefix, &config.suffix);
+        let numeric_type = numeric_type(self, path, config)?;
+        let docs = key_docs(path, config);
+
+        // Tables using an existing type only alias it
+        if let (Table(_), Some(ty)) = (self, user_type(path, config)) {
+            return Ok(quote! {
+                #(#docs)*
+                #visibility mod #mod_ident {
+                    #(#docs)*
+                    pub type #type_ident = #ty;
+                }
+            });
+        }
+
+        // Tables using a shared data type re-export it from the root module
+        if let (Table(_), Some(shared)) = (self, shared_type(path, config)) {
+            let supers = std::iter::repeat_n(quote!(super), module_depth(path));
+            let shared_mod = snake_ident(&shared.name.to_string());
+            let shared_ident =
+                fixed_ident(&shared.name.to_string(), &config.prefix, &config.suffix);
+            let alias =
+                (type_ident != shared_ident).then(|| quote!(pub type #type_ident = #shared_ident;));
+            return Ok(quote! {
+                #(#docs)*
+                #visibility mod #mod_ident {
+                    pub use #(#supers::)*#shared_mod::*;
+                    #alias
+                }
+            });
+        }
+
+        // The root module contains the shared data types
+        let shared_tokens = match path.is_empty() {
+            true => shared_type_tokens(self, config, derive),
+            false => Ok(Vec::new())
+        };
+
+        #[rustfmt::skip]
+        let inner = match (self, config.cow) {
+            (String(_), None) => Ok(quote!(pub type #type_ident = &'static str;)),
+            (String(_), Some(_)) => {
+                Ok(quote!(pub type #type_ident = std::borrow::Cow<'static, str>;))
+            }
+            (Integer(_) | Float(_), _) => Ok(quote!(pub type #type_ident = #numeric_type;)),
+            (Boolean(_), _) => Ok(quote!(pub type #type_ident = bool;)),
+            (Datetime(d), _) => {
+                let datetime_type = datetime_type(d);
+                Ok(quote!(pub type #type_ident = #datetime_type;))
+            }
+            (Array(values), _) => type_tokens::array(values, &type_ident, path, config, derive),
+            (Table(values), _) if is_map(path, config) => {
+                type_tokens::map(values, &type_ident, path, config, derive)
+            }
+            (Table(values), _) => type_tokens::table(values, &type_ident, path, config, derive)
+        };
+
+        // Errors of shared data types don't hide the errors of the root table
+        let (inner, shared_tokens) = match (inner, shared_tokens) {
+            (Ok(inner), Ok(shared_tokens)) => (inner, shared_tokens),
+            (inner, shared_tokens) => {
+                return Err([inner.err(), shared_tokens.err()]
+                    .into_iter()
+                    .flatten()
+                    .map(Err::<(), _>)
+                    .collect_all()
+                    .unwrap_err())
+            }
+        };
+
+        // Arrays and tables document their data types themselves
+        let alias_docs = match self {
+            Array(_) | Table(_) => &[][..],
+            _ => &docs[..]
+        };
+
+        Ok(quote! {
+            #(#docs)*
+            #visibility mod #mod_ident {
+                #(#alias_docs)*
+                #inner
+                #(#shared_tokens)*
+            }
+        })
+    }
+
+    fn static_tokens(
+        &self,
+        key: &str,
+        path: &mut Vec<PathSegment>,
+        config: &StaticTomlAttributes,
+        namespace: &mut Vec<Ident2>
+    ) -> Result<TokenStream2, super::TomlError> {
+        let namespace_ts = quote!(#(#namespace)::*);
+        let numeric_type = numeric_type(self, path, config)?;
+
+        Ok(match (self, config.cow, numeric_type) {
+            (Value::String(s), None, _) => quote!(#s),
+            (Value::String(s), Some(_), _) => quote!(std::borrow::Cow::Borrowed(#s)),
+            (Value::Integer(_) | Value::Float(_), _, Some(ty)) => numeric_tokens(self, &ty, path)?,
+            (Value::Boolean(b), _, _) => quote!(#b),
+
+            (Value::Datetime(d), _, _) => datetime_tokens(d),
+
+            (Value::Array(values), _, _) => {
+                static_tokens::array(values, key, path, config, namespace, namespace_ts)?
+            }
+
+            (Value::Table(values), _, _) if is_map(path, config) => {
+                static_tokens::map(values, key, path, config, namespace, namespace_ts)?
+            }
+
+            (Value::Table(values), _, _) => {
+                static_tokens::table(values, key, path, config, namespace, namespace_ts)?
+            }
+
+            (Value::Integer(_) | Value::Float(_), _, None) => {
+                unreachable!("numeric values always have a numeric type")
+            }
+        })
+    }
+}
+
+/// Generates `impl Default` for every data type representing exactly one
+/// value, returning that value.
+///
+/// Data types shared by the items of slices or the values of maps represent
+/// multiple values and therefore get no implementation, neither do type
+/// aliases.
+/// The implementations use the same absolute paths as the static value and
+/// have to be placed next to it.
+pub(crate) fn default_impls(
+    value: &Value,
+    key: &str,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    namespace: &mut Vec<Ident2>
+) -> Result<Vec<TokenStream2>, super::TomlError> {
+    let is_struct = match value {
+        // Shared data types represent multiple values, existing types are not
+        // generated
+        Value::Table(_) if shared_type(path, config).is_some() => false,
+        Value::Table(_) if user_type(path, config).is_some() => false,
+        Value::Table(_) => true,
+        Value::Array(array) => {
+            !use_slices(array, config) && config.mixed_arrays != Some(MixedArrays::Enum)
+        }
+        _ => false
+    };
+    if !is_struct {
+        return Ok(Vec::new());
+    }
+
+    let namespace_ts = quote!(#(#namespace)::*);
+    let type_ident = fixed_ident(key, &config.prefix, &config.suffix);
+    let static_tokens = value.static_tokens(key, path, config, namespace)?;
+    let mut impls = vec![quote! {
+        #[allow(deprecated)]
+        impl std::default::Default for #namespace_ts::#type_ident {
+            fn default() -> Self {
+                #static_tokens
+            }
+        }
+    }];
+
+    // Fields of structs and items of tuples represent exactly one value
+    let children: Vec<(String, PathSegment, &Value)> = match value {
+        Value::Table(_) if is_map(path, config) => Vec::new(),
+        Value::Table(table) => table
+            .iter()
+            .map(|(k, v)| {
+                Ok((
+                    key_name(k, path, config)?,
+                    PathSegment::Key(k.to_string()),
+                    v
+                ))
+            })
+            .collect_all()?,
+        Value::Array(array) => {
+            let values_ident = config
+                .values_ident
+                .as_ref()
+                .map(Ident2::to_string)
+                .unwrap_or_else(|| String::from("values"));
+            array
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (format!("{values_ident}{i}"), PathSegment::Index(i), v))
+                .collect()
+        }
+        _ => Vec::new()
+    };
+    for (name, segment, child) in children {
+        namespace.push(snake_ident(&name));
+        path.push(segment);
+        let child_impls = default_impls(child, &name, path, config, namespace);
+        path.pop();
+        namespace.pop();
+        impls.extend(child_impls?);
+    }
+
+    Ok(impls)
+}
+
+/// A segment of the path to a value in a TOML file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathSegment {
+    /// A key of a table.
+    Key(String),
+    /// Index of an item in a tuple, every item has its own type.
+    Index(usize),
+    /// Index of an item in a slice, all items share a type.
+    Item(usize),
+    /// Index of the first item of a variant of a mixed array represented as
+    /// enum, every variant has its own type.
+    Variant(usize),
+    /// Key of an entry in a table configured via `map`, all entries share a
+    /// type.
+    Entry(String)
+}
+
+/// Formats the path to a value for error messages, e.g. `database.ports.0`.
+pub(crate) fn path_string(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) | PathSegment::Entry(key) => key.to_string(),
+            PathSegment::Index(i) | PathSegment::Item(i) | PathSegment::Variant(i) => i.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Formats the path to a value as written in attributes, e.g.
+/// `servers.*."1st"`.
+///
+/// Keys that are not identifiers are quoted, items of slices and entries of
+/// maps are only matched by wildcards.
+pub(crate) fn key_path_string(path: &[PathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            PathSegment::Key(key) if syn::parse_str::<syn::Ident>(key).is_ok() => key.to_string(),
+            PathSegment::Key(key) => format!("{key:?}"),
+            PathSegment::Index(i) => i.to_string(),
+            PathSegment::Item(_) | PathSegment::Variant(_) | PathSegment::Entry(_) => {
+                "*".to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Extension for collecting results without stopping at the first error.
+pub(crate) trait CollectAll<T> {
+    /// Collects all values, or all errors if any of the results failed.
+    ///
+    /// This allows reporting every invalid value of a TOML file at once.
+    fn collect_all(self) -> Result<Vec<T>, super::TomlError>;
+}
+
+impl<T, I: Iterator<Item = Result<T, super::TomlError>>> CollectAll<T> for I {
+    fn collect_all(self) -> Result<Vec<T>, super::TomlError> {
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for result in self {
+            match result {
+                Ok(value) => values.push(value),
+                Err(e) => errors.extend(e.into_errors())
+            }
+        }
+
+        match errors.len() {
+            0 => Ok(values),
+            1 => Err(errors.remove(0)),
+            _ => Err(super::TomlError::Multiple(errors))
+        }
+    }
+}
+
+/// Creates an identifier with optional prefix and suffix.
+///
+/// Given an identifier, a prefix and a suffix, it constructs a new identifier
+/// concatenating the prefix, identifier, and suffix.
+/// Identifiers starting with a digit are prefixed by `_`, `Self` becomes
+/// `Self_`.
+pub fn fixed_ident(ident: &str, prefix: &Option<Ident2>, suffix: &Option<Ident2>) -> Ident2 {
+    let prefix = prefix.as_ref().map(Ident2::to_string).unwrap_or_default();
+    let suffix = suffix.as_ref().map(Ident2::to_string).unwrap_or_default();
+    let ident = format!("{prefix}{}{suffix}", ident.to_case(Case::Pascal));
+    match ident.as_str() {
+        ident if ident.starts_with(|c: char| c.is_ascii_digit()) => format_ident!("_{ident}"),
+        "Self" => format_ident!("Self_"),
+        ident => format_ident!("{ident}")
+    }
+}
+
+/// Removes the values configured via `skip` from a TOML value.
+///
+/// Skipped values are removed before any code is generated, so they are
+/// neither part of the data types nor of the static value.
+/// Tables left empty by skipping all of their keys are removed as well, items
+/// of arrays are kept to not change their length.
+/// For every skip pattern, `matched` records whether it removed a value.
+pub(crate) fn skip_values(
+    value: &mut Value,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    matched: &mut [bool]
+) {
+    match value {
+        Value::Table(table) => {
+            let is_map = is_map(path, config);
+            table.retain(|k, v| {
+                path.push(match is_map {
+                    true => PathSegment::Entry(k.to_string()),
+                    false => PathSegment::Key(k.to_string())
+                });
+                let mut skip = false;
+                for (skip_path, matched) in config.skips.iter().zip(matched.iter_mut()) {
+                    if skip_path.matches(path) {
+                        *matched = true;
+                        skip = true;
+                    }
+                }
+                if !skip {
+                    let was_empty = matches!(v, Value::Table(table) if table.is_empty());
+                    skip_values(v, path, config, matched);
+                    skip = !was_empty && matches!(v, Value::Table(table) if table.is_empty());
+                }
+                path.pop();
+                !skip
+            });
+        }
+
+        Value::Array(array) => {
+            let use_slices = use_slices(array, config);
+            for (i, v) in array.iter_mut().enumerate() {
+                path.push(match use_slices {
+                    true => PathSegment::Item(i),
+                    false => PathSegment::Index(i)
+                });
+                skip_values(v, path, config, matched);
+                path.pop();
+            }
+        }
+
+        _ => ()
+    }
+}
+
+/// Replaces `${VAR}` and `${VAR:-default}` in all strings with the values of
+/// the environment variables at expansion time.
+///
+/// A default is used if the variable is unset or empty, `$${` is a literal
+/// `${`.
+/// The names of all variables read are collected in `vars`, so the generated
+/// code can track them.
+pub(crate) fn interpolate_values(
+    value: &mut Value,
+    path: &mut Vec<PathSegment>,
+    vars: &mut BTreeSet<String>
+) -> Result<(), super::TomlError> {
+    match value {
+        Value::String(s) => {
+            *s = interpolate(s, path, vars)?;
+            Ok(())
+        }
+
+        Value::Table(table) => table
+            .iter_mut()
+            .map(|(k, v)| {
+                path.push(PathSegment::Key(k.to_string()));
+                let interpolated = interpolate_values(v, path, vars);
+                path.pop();
+                interpolated
+            })
+            .collect_all()
+            .map(|_: Vec<()>| ()),
+
+        Value::Array(array) => array
+            .iter_mut()
+            .enumerate()
+            .map(|(i, v)| {
+                path.push(PathSegment::Index(i));
+                let interpolated = interpolate_values(v, path, vars);
+                path.pop();
+                interpolated
+            })
+            .collect_all()
+            .map(|_: Vec<()>| ()),
+
+        _ => Ok(())
+    }
+}
+
+/// Interpolates the environment variables in a single string.
+fn interpolate(
+    s: &str,
+    path: &[PathSegment],
+    vars: &mut BTreeSet<String>
+) -> Result<String, super::TomlError> {
+    let invalid = || super::TomlError::InterpolationInvalid {
+        path: path.to_vec(),
+        value: s.to_string()
+    };
+
+    let mut interpolated = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('$') {
+        interpolated.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("$${") {
+            interpolated.push_str("${");
+            rest = after;
+            continue;
+        }
+        let Some(after) = rest.strip_prefix("${")
+        else {
+            interpolated.push('$');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let end = after.find('}').ok_or_else(invalid)?;
+        let (name, default) = match after[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&after[..end], None)
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(invalid());
+        }
+
+        vars.insert(name.to_string());
+        match (std::env::var(name), default) {
+            (Ok(value), Some(default)) if value.is_empty() => interpolated.push_str(default),
+            (Ok(value), _) => interpolated.push_str(&value),
+            (Err(_), Some(default)) => interpolated.push_str(default),
+            (Err(_), None) => {
+                return Err(super::TomlError::EnvVarMissing {
+                    path: path.to_vec(),
+                    name: name.to_string()
+                })
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    interpolated.push_str(rest);
+
+    Ok(interpolated)
+}
+
+/// Determines if slices should be used for TOML arrays based on the
+/// configuration.
+///
+/// Returns `true` if slices should be used instead of arrays based on the
+/// configuration and the content of the array.
+fn use_slices(array: &Array, config: &StaticTomlAttributes) -> bool {
+    // If prefer_slices is explicitly set to false, return false.
+    if !config
+        .prefer_slices
+        .as_ref()
+        .map(|b| b.value())
+        .unwrap_or(true)
+    {
+        return false;
+    }
+
+    // Tables with differing keys may be unified into a single type.
+    if config.unify_tables.is_some() && unify_array(array).is_some() {
+        return true;
+    }
+
+    // Check if all elements in the array are of the same type.
+    array
+        .iter()
+        .zip(array.iter().skip(1))
+        .map(|(a, b)| a.type_eq(b))
+        .reduce(|acc, b| acc && b)
+        .unwrap_or(true)
+}
+
+/// Returns the Rust type of a value that gets no module of its own, which are
+/// leaf values and slices of them with `layout = flat`.
+///
+/// Returns `None` for values that need a data type in a module and for every
+/// value with the nested layout.
+fn flat_type(
+    value: &Value,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes
+) -> Result<Option<TokenStream2>, super::TomlError> {
+    if config.layout != Some(Layout::Flat) {
+        return Ok(None);
+    }
+
+    let numeric_type = numeric_type(value, path, config)?;
+    Ok(match (value, config.cow) {
+        (Value::String(_), None) => Some(quote!(&'static str)),
+        (Value::String(_), Some(_)) => Some(quote!(std::borrow::Cow<'static, str>)),
+        (Value::Integer(_) | Value::Float(_), _) => numeric_type.map(|ty| quote!(#ty)),
+        (Value::Boolean(_), _) => Some(quote!(bool)),
+        (Value::Datetime(d), _) => Some(datetime_type(d)),
+        (Value::Array(array), cow) if use_slices(array, config) => {
+            let len = array.len();
+            let Some(first) = array.first()
+            else {
+                return Ok(Some(quote!([(); 0])));
+            };
+            path.push(PathSegment::Item(0));
+            let item_type = flat_type(first, path, config);
+            path.pop();
+            item_type?.map(|item_type| match cow {
+                None => quote!([#item_type; #len]),
+                Some(_) => quote!(std::borrow::Cow<'static, [#item_type]>)
+            })
+        }
+        (Value::Table(_), _) => user_type(path, config).map(|ty| quote!(#ty)),
+        (Value::Array(_), _) => None
+    })
+}
+
+/// Returns the data type shared by the table at the path, if configured via
+/// `shared`.
+///
+/// If multiple patterns match, the last one wins.
+/// Tables configured as maps never use a shared data type.
+fn shared_type<'a>(
+    path: &[PathSegment],
+    config: &'a StaticTomlAttributes
+) -> Option<&'a SharedType> {
+    match is_map(path, config) || user_type(path, config).is_some() {
+        true => None,
+        false => config
+            .shared
+            .iter()
+            .rev()
+            .find(|shared| shared.path.matches(path))
+    }
+}
+
+/// Generates the doc attributes of the value at the path from the comment
+/// preceding its key in the TOML files.
+fn key_docs(path: &[PathSegment], config: &StaticTomlAttributes) -> Vec<TokenStream2> {
+    let Some(comment) = config.comments.get(&comment_key(path))
+    else {
+        return Vec::new();
+    };
+    comment
+        .lines()
+        .map(|line| {
+            let line = format!(" {line}");
+            quote!(#[doc = #line])
+        })
+        .collect()
+}
+
+/// Returns the attributes configured via `attr` for the value at the path, in
+/// the order they are configured.
+fn path_attrs<'a>(path: &[PathSegment], config: &'a StaticTomlAttributes) -> Vec<&'a Attribute> {
+    config
+        .path_attrs
+        .iter()
+        .filter(|path_attrs| path_attrs.path.matches(path))
+        .flat_map(|path_attrs| path_attrs.attrs.iter())
+        .collect()
+}
+
+/// Returns the attributes configured via `attr` for the field or variant
+/// containing the value at the path.
+///
+/// Values with a generated data type carry their attributes on that type, all
+/// other values on their field or variant.
+fn field_attrs<'a>(
+    value: &Value,
+    path: &[PathSegment],
+    config: &'a StaticTomlAttributes
+) -> Vec<&'a Attribute> {
+    let has_type = match value {
+        Value::Table(_) => shared_type(path, config).is_none() && user_type(path, config).is_none(),
+        Value::Array(array) => !use_slices(array, config),
+        _ => false
+    };
+    match has_type {
+        true => Vec::new(),
+        false => path_attrs(path, config)
+    }
+}
+
+/// Returns the existing type used for the table at the path, if configured via
+/// `use_type`.
+///
+/// If multiple patterns match, the last one wins.
+/// Tables configured as maps never use an existing type.
+fn user_type<'a>(path: &[PathSegment], config: &'a StaticTomlAttributes) -> Option<&'a syn::Path> {
+    match is_map(path, config) {
+        true => None,
+        false => config
+            .user_types
+            .iter()
+            .rev()
+            .find(|user_type| user_type.path.matches(path))
+            .map(|user_type| &user_type.ty)
+    }
+}
+
+/// Checks whether the value at the path is a field of an existing type
+/// configured via `use_type`, or an item of a slice that is such a field.
+fn in_user_type(path: &[PathSegment], config: &StaticTomlAttributes) -> bool {
+    let items = path
+        .iter()
+        .rev()
+        .take_while(|segment| matches!(segment, PathSegment::Item(_)))
+        .count();
+    match path[..path.len() - items].split_last() {
+        Some((PathSegment::Key(_), parent)) => user_type(parent, config).is_some(),
+        _ => false
+    }
+}
+
+/// Checks that the values of a table can be embedded into the existing type
+/// configured via `use_type`.
+///
+/// There are no generated data types within existing types, so nested tables
+/// need an existing type themselves and maps and tuples are not supported.
+/// Keys without a field are reported if the fields of the type are known,
+/// otherwise rustc checks them.
+fn check_user_type(
+    table: &Table,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    ty: &syn::Path
+) -> Result<(), super::TomlError> {
+    fn check_value(
+        value: &Value,
+        path: &mut Vec<PathSegment>,
+        config: &StaticTomlAttributes,
+        ty: &syn::Path
+    ) -> Result<(), super::TomlError> {
+        let invalid = |reason| super::TomlError::UserTypeInvalid {
+            path: path.clone(),
+            ty: ty.to_token_stream().to_string().replace(' ', ""),
+            reason
+        };
+        match value {
+            Value::Table(_) if is_map(path, config) => {
+                Err(invalid("maps need a generated data type"))
+            }
+            Value::Table(_) if user_type(path, config).is_none() => Err(invalid(
+                "nested tables need an existing type configured via `use_type`"
+            )),
+            Value::Array(array) if use_slices(array, config) => array
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    path.push(PathSegment::Item(i));
+                    let checked = check_value(item, path, config, ty);
+                    path.pop();
+                    checked
+                })
+                .collect_all()
+                .map(|_: Vec<()>| ()),
+            Value::Array(_) => Err(invalid("mixed arrays need a generated data type")),
+            _ => Ok(())
+        }
+    }
+
+    let fields = ty.segments.last().and_then(|segment| {
+        config
+            .user_type_fields
+            .get(&segment.ident.unraw().to_string())
+    });
+    table
+        .iter()
+        .map(|(k, v)| {
+            let field =
+                key_name(k, path, config).map(|name| snake_ident(&name).unraw().to_string());
+            path.push(PathSegment::Key(k.to_string()));
+            let checked = match (fields, field) {
+                (Some(fields), Ok(field)) if !fields.contains(&field) => {
+                    Err(super::TomlError::UserTypeInvalid {
+                        path: path.clone(),
+                        ty: ty.to_token_stream().to_string().replace(' ', ""),
+                        reason: "it has no such field"
+                    })
+                }
+                _ => check_value(v, path, config, ty)
+            };
+            path.pop();
+            checked
+        })
+        .collect_all()
+        .map(|_: Vec<()>| ())
+}
+
+/// The number of modules between the root module and the module of the value
+/// at the path.
+///
+/// Every segment has its own module, variants of enums are additionally
+/// nested in the module of the enum.
+fn module_depth(path: &[PathSegment]) -> usize {
+    path.len() +
+        path.iter()
+            .filter(|segment| matches!(segment, PathSegment::Variant(_)))
+            .count()
+}
+
+/// Generates the modules of the data types configured via `shared`.
+///
+/// Every shared data type is generated from the first table using it, all
+/// other tables using it must have the same data type.
+/// Shared data types are placed into the root module, their modules must not
+/// collide with the keys of the root table.
+fn shared_type_tokens(
+    root: &Value,
+    config: &StaticTomlAttributes,
+    derive: &[Attribute]
+) -> Result<Vec<TokenStream2>, super::TomlError> {
+    if config.shared.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut shared = Vec::new();
+    shared_tables(root, &mut Vec::new(), config, &mut shared, None)?;
+
+    // Invalid root keys are reported by the root table itself
+    let root_keys: Vec<(&str, String)> = match root {
+        Value::Table(table) => table
+            .keys()
+            .filter_map(|k| Some((k.as_str(), key_name(k, &[], config).ok()?)))
+            .collect(),
+        _ => Vec::new()
+    };
+    shared
+        .into_iter()
+        .map(|(name, value, mut path)| {
+            let name = name.to_string();
+            let mod_ident = snake_ident(&name);
+            if let Some((key, _)) = root_keys.iter().find(|(_, k)| snake_ident(k) == mod_ident) {
+                return Err(super::TomlError::KeyCollision {
+                    path: vec![PathSegment::Key(key.to_string())],
+                    first: key.to_string(),
+                    second: name,
+                    ident: mod_ident.to_string()
+                });
+            }
+
+            let Value::Table(table) = value
+            else {
+                unreachable!("only tables use shared data types")
+            };
+            let type_ident = fixed_ident(&name, &config.prefix, &config.suffix);
+            let inner = type_tokens::table(table, &type_ident, &mut path, config, derive)?;
+            Ok(quote! {
+                pub mod #mod_ident {
+                    #inner
+                }
+            })
+        })
+        .collect_all()
+}
+
+/// Collects the first table of every shared data type.
+///
+/// Fails if a table does not match the data type of the first one or if it is
+/// nested in a table that uses a shared data type itself.
+fn shared_tables<'v>(
+    value: &'v Value,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    shared: &mut Vec<(Ident2, &'v Value, Vec<PathSegment>)>,
+    mut nested_in: Option<Ident2>
+) -> Result<(), super::TomlError> {
+    if let (Value::Table(_), Some(shared_type)) = (value, shared_type(path, config)) {
+        let invalid = |reason: String| super::TomlError::SharedInvalid {
+            path: path.clone(),
+            name: shared_type.name.to_string(),
+            reason
+        };
+        if let Some(outer) = nested_in {
+            return Err(invalid(format!(
+                "it is nested in the shared type `{outer}`"
+            )));
+        }
+        match shared.iter().find(|(name, ..)| *name == shared_type.name) {
+            Some((_, first, first_path)) if !first.type_eq(value) => {
+                return Err(invalid(format!(
+                    "its data type differs from `{}`",
+                    path_string(first_path)
+                )));
+            }
+            Some(_) => (),
+            None => shared.push((shared_type.name.clone(), value, path.clone()))
+        }
+        nested_in = Some(shared_type.name.clone());
+    }
+
+    let children: Vec<(PathSegment, &Value)> = match value {
+        Value::Table(table) => {
+            let is_map = is_map(path, config);
+            table
+                .iter()
+                .map(|(k, v)| match is_map {
+                    true => (PathSegment::Entry(k.to_string()), v),
+                    false => (PathSegment::Key(k.to_string()), v)
+                })
+                .collect()
+        }
+        Value::Array(array) => {
+            let use_slices = use_slices(array, config);
+            let is_enum = !use_slices && config.mixed_arrays == Some(MixedArrays::Enum);
+            array
+                .iter()
+                .enumerate()
+                .map(|(i, v)| match (use_slices, is_enum) {
+                    (true, _) => (PathSegment::Item(i), v),
+                    (false, true) => (PathSegment::Variant(i), v),
+                    (false, false) => (PathSegment::Index(i), v)
+                })
+                .collect()
+        }
+        _ => Vec::new()
+    };
+    children
+        .into_iter()
+        .map(|(segment, child)| {
+            path.push(segment);
+            let collected = shared_tables(child, path, config, shared, nested_in.clone());
+            path.pop();
+            collected
+        })
+        .collect_all()
+        .map(|_: Vec<()>| ())
+}
+
+/// Checks whether the table at the path is configured to be a map.
+fn is_map(path: &[PathSegment], config: &StaticTomlAttributes) -> bool {
+    map_backend(path, config).is_some()
+}
+
+/// Returns the backend of the map at the path, if the table at the path is
+/// configured via `map`.
+///
+/// If multiple maps match the path, the last one wins.
+fn map_backend(path: &[PathSegment], config: &StaticTomlAttributes) -> Option<MapBackend> {
+    config
+        .maps
+        .iter()
+        .rev()
+        .find(|map| map.path.matches(path))
+        .map(|map| map.backend)
+}
+
+/// Determines the shared type of the values of a table configured via `map`.
+///
+/// Like slices, maps require all values to share the same type.
+/// If `unify_tables` is set, tables are unified instead, in that case the
+/// unified table is returned.
+fn map_values<'a>(
+    table: &'a Table,
+    path: &[PathSegment],
+    config: &StaticTomlAttributes
+) -> Result<Option<UnifiedTable<'a>>, super::TomlError> {
+    // Values using an existing type share it, whatever their keys are
+    if let Some(k) = table.keys().next() {
+        let entry_path = [path, &[PathSegment::Entry(k.to_string())]].concat();
+        if table.values().all(Value::is_table) && user_type(&entry_path, config).is_some() {
+            return Ok(None);
+        }
+    }
+
+    if config.unify_tables.is_some() {
+        let tables = table
+            .values()
+            .map(|v| v.as_table())
+            .collect::<Option<Vec<&Table>>>();
+        if let Some(unified) = tables
+            .filter(|t| !t.is_empty())
+            .and_then(|t| unify_tables(&t))
+        {
+            return Ok(Some(unified));
+        }
+    }
+
+    let values: Vec<&Value> = table.values().collect();
+    match values
+        .iter()
+        .zip(values.iter().skip(1))
+        .all(|(a, b)| a.type_eq(b))
+    {
+        true => Ok(None),
+        false => Err(super::TomlError::MapInvalid(path.to_vec()))
+    }
+}
+
+/// Schema of the tables in an array, merged for `unify_tables`.
+pub(crate) struct UnifiedTable<'a> {
+    /// Union of the keys of all tables, sorted like [`Table`].
+    pub fields: BTreeMap<&'a str, UnifiedField<'a>>
+}
+
+/// A field of a [`UnifiedTable`].
+pub(crate) struct UnifiedField<'a> {
+    /// Whether the key is missing in some of the tables.
+    pub optional: bool,
+    pub kind: UnifiedKind<'a>
+}
+
+/// Data type of a [`UnifiedField`].
+pub(crate) enum UnifiedKind<'a> {
+    /// All present values share this value's type.
+    Value(&'a Value),
+    /// All present values are tables, which are unified as well.
+    Table(UnifiedTable<'a>)
+}
+
+/// Unifies the tables of an array into a single schema.
+///
+/// Returns `None` if the array is empty, contains values other than tables or
+/// if values under the same key cannot be represented by the same type.
+/// Nested tables are unified recursively, all other values need to be equal
+/// in type via [`TomlTokens::type_eq`].
+fn unify_array(array: &Array) -> Option<UnifiedTable<'_>> {
+    let tables = array
+        .iter()
+        .map(|v| v.as_table())
+        .collect::<Option<Vec<&Table>>>()?;
+    match tables.is_empty() {
+        true => None,
+        false => unify_tables(&tables)
+    }
+}
+
+fn unify_tables<'a>(tables: &[&'a Table]) -> Option<UnifiedTable<'a>> {
+    let keys: BTreeSet<&str> = tables
+        .iter()
+        .flat_map(|t| t.keys())
+        .map(String::as_str)
+        .collect();
+
+    let mut fields = BTreeMap::new();
+    for key in keys {
+        let present: Vec<&Value> = tables.iter().filter_map(|t| t.get(key)).collect();
+        let optional = present.len() < tables.len();
+
+        let nested = present
+            .iter()
+            .map(|v| v.as_table())
+            .collect::<Option<Vec<&Table>>>();
+        let kind = match nested {
+            Some(nested) => UnifiedKind::Table(unify_tables(&nested)?),
+            None => {
+                let first = present[0];
+                if !present.iter().all(|v| first.type_eq(v)) {
+                    return None;
+                }
+                UnifiedKind::Value(first)
+            }
+        };
+
+        fields.insert(key, UnifiedField { optional, kind });
+    }
+
+    Some(UnifiedTable { fields })
+}
+
+/// Resolves the Rust type of a numeric value.
+///
+/// Integers default to `i64` and floats to `f64`, unless configured
+/// otherwise via the `int` and `float` attributes or a `type` override.
+/// An override may target the value directly or, if the override is an array
+/// type like `[u16]`, one of the arrays containing the value.
+/// The last matching override wins.
+///
+/// Returns `None` for values that are not numeric and errors if an override
+/// targets a value it cannot be applied to.
+fn numeric_type(
+    value: &Value,
+    path: &[PathSegment],
+    config: &StaticTomlAttributes
+) -> Result<Option<Ident2>, super::TomlError> {
+    let mut resolved = None;
+    for type_override in config.types.iter() {
+        // Unwrap the override for every array item level between the targeted
+        // array and this value.
+        let mut ty = &type_override.ty;
+        let mut depth = 0;
+        loop {
+            let is_item_path = path.len() >= depth &&
+                path[path.len() - depth..].iter().all(|s| {
+                    matches!(
+                        s,
+                        PathSegment::Index(_) | PathSegment::Item(_) | PathSegment::Variant(_)
+                    )
+                });
+            if is_item_path && type_override.path.matches(&path[..path.len() - depth]) {
+                resolved = Some(ty);
+            }
+
+            match ty {
+                TypeSpec::Array(inner) => {
+                    ty = inner;
+                    depth += 1;
+                }
+                TypeSpec::Numeric(_) => break
+            }
+        }
+    }
+
+    let invalid = |ty: &TypeSpec| super::TomlError::TypeOverrideInvalid {
+        path: path.to_vec(),
+        ty: ty.to_string(),
+        found: value.type_str()
+    };
+
+    match (value, resolved) {
+        (Value::Integer(_), Some(TypeSpec::Numeric(ty))) => Ok(Some(ty.clone())),
+        (Value::Float(_), Some(TypeSpec::Numeric(ty))) if FLOAT_TYPES.iter().any(|f| ty == f) => {
+            Ok(Some(ty.clone()))
+        }
+        (Value::Array(_), Some(TypeSpec::Array(_))) | (_, None) => Ok(match value {
+            Value::Integer(_) => Some(config.int.clone().unwrap_or(format_ident!("i64"))),
+            Value::Float(_) => Some(config.float.clone().unwrap_or(format_ident!("f64"))),
+            _ => None
+        }),
+        (_, Some(ty)) => Err(invalid(ty))
+    }
+}
+
+/// Generates the literal for a numeric value of the given type.
+///
+/// Errors if the value does not fit into the type.
+fn numeric_tokens(
+    value: &Value,
+    ty: &Ident2,
+    path: &[PathSegment]
+) -> Result<TokenStream2, super::TomlError> {
+    let out_of_range = || super::TomlError::ValueOutOfRange {
+        path: path.to_vec(),
+        value: value.to_string(),
+        ty: ty.to_string()
+    };
+
+    macro_rules! int_literal {
+        ($i:expr, $($ty:ident => $suffixed:ident),*) => {
+            match ty.to_string().as_str() {
+                $(stringify!($ty) => Literal::$suffixed($ty::try_from($i).map_err(|_| out_of_range())?),)*
+                _ => return float_tokens($i as f64, ty).ok_or_else(out_of_range)
+            }
+        };
+    }
+
+    let literal = match value {
+        Value::Integer(i) => int_literal!(
+            *i,
+            i8 => i8_suffixed, i16 => i16_suffixed, i32 => i32_suffixed,
+            i64 => i64_suffixed, i128 => i128_suffixed, isize => isize_suffixed,
+            u8 => u8_suffixed, u16 => u16_suffixed, u32 => u32_suffixed,
+            u64 => u64_suffixed, u128 => u128_suffixed, usize => usize_suffixed
+        ),
+        Value::Float(f) => return float_tokens(*f, ty).ok_or_else(out_of_range),
+        _ => unreachable!("only called for numeric values")
+    };
+
+    Ok(literal.into_token_stream())
+}
+
+/// Generates the tokens for a float of type `f32` or `f64`.
+///
+/// Non-finite values are emitted as the associated constants of the type.
+/// Returns `None` if a finite value does not fit into `f32`.
+fn float_tokens(f: f64, ty: &Ident2) -> Option<TokenStream2> {
+    if f.is_nan() {
+        return Some(quote!(#ty::NAN));
+    }
+    if f.is_infinite() {
+        return Some(match f.is_sign_positive() {
+            true => quote!(#ty::INFINITY),
+            false => quote!(#ty::NEG_INFINITY)
+        });
+    }
+
+    match ty == "f32" {
+        true if (f as f32).is_infinite() => None,
+        true => Some(Literal::f32_suffixed(f as f32).into_token_stream()),
+        false => Some(Literal::f64_suffixed(f).into_token_stream())
+    }
+}
+
+/// Returns the type from `static_toml::datetime` matching the kind of the
+/// TOML datetime.
+fn datetime_type(datetime: &Datetime) -> TokenStream2 {
+    match (datetime.date, datetime.time, datetime.offset) {
+        (Some(_), Some(_), Some(_)) => quote!(::static_toml::datetime::OffsetDatetime),
+        (Some(_), Some(_), None) => quote!(::static_toml::datetime::LocalDatetime),
+        (Some(_), None, _) => quote!(::static_toml::datetime::LocalDate),
+        (None, _, _) => quote!(::static_toml::datetime::LocalTime)
+    }
+}
+
+/// Generates the const constructor call for a TOML datetime.
+fn datetime_tokens(datetime: &Datetime) -> TokenStream2 {
+    let date = datetime.date.map(|d| {
+        let (year, month, day) = (d.year, d.month, d.day);
+        quote!(::static_toml::datetime::LocalDate::new(#year, #month, #day))
+    });
+    let time = datetime.time.map(|t| {
+        let (hour, minute, second, nanosecond) = (t.hour, t.minute, t.second, t.nanosecond);
+        quote!(::static_toml::datetime::LocalTime::new(#hour, #minute, #second, #nanosecond))
+    });
+    let offset = datetime.offset.map(|o| match o {
+        Offset::Z => quote!(::static_toml::datetime::Offset::Z),
+        Offset::Custom { minutes } => quote!(::static_toml::datetime::Offset::custom(#minutes))
+    });
+
+    let datetime_type = datetime_type(datetime);
+    match (date, time, offset) {
+        (Some(date), Some(time), Some(offset)) => {
+            quote!(#datetime_type::new(#date, #time, #offset))
+        }
+        (Some(date), Some(time), None) => quote!(#datetime_type::new(#date, #time)),
+        (Some(date), None, _) => date,
+        (None, Some(time), _) => time,
+        (None, None, _) => unreachable!("TOML datetimes always have a date or a time")
+    }
+}
+
+/// Groups the elements of a mixed array by their types.
+///
+/// Used when mixed arrays are represented as enums.
+/// Returns the keys of the variants, each with a representative value, in
+/// order of their first appearance, and for every element the index of its
+/// variant.
+/// Variants are named after the kind of their values, e.g. `string` or
+/// `table`.
+/// If multiple variants share a kind, an index is appended, e.g. `table0` and
+/// `table1`.
+fn enum_variants(array: &Array) -> (Vec<(String, &Value)>, Vec<usize>) {
+    // Deduplicate the element types.
+    let mut representatives: Vec<&Value> = Vec::new();
+    let mut indices = Vec::with_capacity(array.len());
+    for value in array {
+        match representatives.iter().position(|r| r.type_eq(value)) {
+            Some(i) => indices.push(i),
+            None => {
+                indices.push(representatives.len());
+                representatives.push(value);
+            }
+        }
+    }
+
+    // Name the variants after the kinds of their values.
+    let kinds: Vec<&str> = representatives.iter().map(|v| v.type_str()).collect();
+    let variants = representatives
+        .iter()
+        .zip(kinds.iter())
+        .enumerate()
+        .map(|(i, (value, kind))| {
+            let same_kind = kinds.iter().filter(|k| *k == kind).count();
+            match same_kind {
+                1 => (kind.to_string(), *value),
+                _ => {
+                    let n = kinds[..i].iter().filter(|k| *k == kind).count();
+                    (format!("{kind}{n}"), *value)
+                }
+            }
+        })
+        .collect();
+
+    (variants, indices)
+}
+
+/// Creates the `snake_case` identifier of a name, used for fields and modules.
+///
+/// Keywords become raw identifiers, like `r#type`, names starting with a digit
+/// are prefixed by `_`.
+/// `self`, `super` and `crate` cannot be raw identifiers and get a trailing
+/// `_` instead.
+fn snake_ident(name: &str) -> Ident2 {
+    let ident = name.to_case(Case::Snake);
+    let ident = match ident.starts_with(|c: char| c.is_ascii_digit()) {
+        true => format!("_{ident}"),
+        false => ident
+    };
+
+    match ident.as_str() {
+        "self" | "super" | "crate" => format_ident!("{ident}_"),
+        _ if syn::parse_str::<Ident2>(&ident).is_err() => {
+            Ident2::new_raw(&ident, Span2::call_site())
+        }
+        _ => format_ident!("{ident}")
+    }
+}
+
+/// Generates `#[serde(rename = "key")]` for a field, if serde is used and
+/// the identifier of the field differs from the key.
+fn serde_rename(
+    key: &str,
+    field_key: &Ident2,
+    config: &StaticTomlAttributes
+) -> Option<TokenStream2> {
+    // serde strips the `r#` of raw identifiers itself.
+    match config.serde.is_some() && field_key.unraw() != key {
+        true => Some(quote!(#[serde(rename = #key)])),
+        false => None
+    }
+}
+
+/// Determines the name of a key, used for its identifiers.
+///
+/// Renames take precedence, otherwise keys that cannot be converted into an
+/// identifier are handled by the `key_fallback`, or are an error without one.
+fn key_name(
+    key: &str,
+    path: &[PathSegment],
+    config: &StaticTomlAttributes
+) -> Result<String, super::TomlError> {
+    let mut key_path = path.to_vec();
+    key_path.push(PathSegment::Key(key.to_string()));
+    if let Some(rename) = config
+        .renames
+        .iter()
+        .rev()
+        .find(|r| r.path.matches(&key_path))
+    {
+        return Ok(rename.name.to_string());
+    }
+
+    let name = match config.key_fallback {
+        Some(KeyFallback::Transliterate) => deunicode::deunicode(key),
+        _ => key.to_string()
+    };
+    if is_valid_identifier(name.to_case(Case::Snake).as_str()) {
+        return Ok(name);
+    }
+
+    // Digits at the start are handled by the identifiers themselves.
+    let name: String = name
+        .chars()
+        .map(|c| match c.is_alphanumeric() {
+            true => c,
+            false => '_'
+        })
+        .collect();
+    match (config.key_fallback, name.chars().any(char::is_alphanumeric)) {
+        (Some(_), true) => Ok(name),
+        _ => Err(super::TomlError::KeyInvalid(key.to_string(), key_path))
+    }
+}
+
+/// Checks that the identifiers of a key do not collide with the ones of the
+/// previous keys of the same table.
+///
+/// Different keys may convert to the same field, module or type identifier,
+/// e.g. `fooBar` and `foo_bar` both become the field `foo_bar`.
+/// The keys are compared by their names, see [`key_name`].
+fn check_collision<'a>(
+    (key, name): (&str, &str),
+    previous: impl IntoIterator<Item = (&'a str, &'a str)>,
+    path: &[PathSegment],
+    config: &StaticTomlAttributes
+) -> Result<(), super::TomlError> {
+    let idents = |name: &str| {
+        [
+            snake_ident(name).to_string(),
+            fixed_ident(name, &config.prefix, &config.suffix).to_string()
+        ]
+    };
+
+    let key_idents = idents(name);
+    for (other, other_name) in previous {
+        let other_idents = idents(other_name);
+        if let Some((ident, _)) = key_idents
+            .iter()
+            .zip(other_idents.iter())
+            .find(|(ident, other_ident)| ident == other_ident)
+        {
+            let mut path = path.to_vec();
+            path.push(PathSegment::Key(key.to_string()));
+            return Err(super::TomlError::KeyCollision {
+                path,
+                first: other.to_string(),
+                second: key.to_string(),
+                ident: ident.to_string()
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn is_valid_identifier(input: &str) -> bool {
+    let mut chars = input.chars();
+
+    // First char must be a letter or underscore.
+    let Some(first) = chars.next()
+    else {
+        return false;
+    };
+    if !(first.is_alphabetic() || first == '_') {
+        return false;
+    }
+
+    // All others must be must be numbers, letters or underscore.
+    chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Generate the auto doc comment for the statics.
+///
+/// Takes a label, like the quoted path, and the content of every included
+/// file, each file gets its own code block.
+pub fn gen_auto_doc(files: &[(String, &str)], storage_class: &StorageClass) -> TokenStream2 {
+    let storage_class = match storage_class {
+        StorageClass::Static(_) => "Static",
+        StorageClass::Const(_) => "Constant"
+    };
+    let paths: Vec<&str> = files.iter().map(|(label, _)| label.as_str()).collect();
+    let summary = match paths.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            format!(
+                "{storage_class} inclusion of {} merged with {last}.",
+                rest.join(", ")
+            )
+        }
+        _ => format!("{storage_class} inclusion of {}.", paths.join(""))
+    };
+    let blocks = files.iter().map(|(path, content)| {
+        let (heading, separator) = match files.len() {
+            1 => (quote!(), quote!()),
+            _ => {
+                let heading = format!("{path}:");
+                (quote!(#[doc = #heading] #[doc = ""]), quote!(#[doc = ""]))
+            }
+        };
+        quote! {
+            #heading
+            #[doc = "```toml"]
+            #[doc = #content]
+            #[doc = "```"]
+            #separator
+        }
+    });
+    quote! {
+        #[doc = ""]
+        #[doc = #summary]
+        #[doc = ""]
+        #(#blocks)*
+    }
+}
@@ -0,0 +1,290 @@
+//! Generates Rust tokens for overriding static data at runtime.
+//!
+//! The `runtime_tokens` submodule implements `static_toml::runtime::Layer` for
+//! the generated data types, allowing to merge TOML files and environment
+//! variables read at runtime into the static data.
+//! The implementations are only generated with the `runtime` feature and for
+//! `cow` data types, as only these can own runtime values.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Ident as Ident2, Index};
+
+use crate::parse::{MapBackend, StaticTomlAttributes};
+
+/// Returns `true` if runtime overrides are generated.
+fn enabled(config: &StaticTomlAttributes) -> bool {
+    cfg!(feature = "runtime") && config.cow.is_some()
+}
+
+/// A field of a struct as read from a runtime table.
+pub(crate) struct Field<'a> {
+    pub key: &'a str,
+    pub ident: &'a Ident2,
+    pub optional: bool
+}
+
+/// Generates the `Layer` implementation for a struct representing a table.
+///
+/// Tables are merged key by key, unknown keys are an error.
+pub(crate) fn table(
+    type_ident: &Ident2,
+    fields: &[Field],
+    config: &StaticTomlAttributes
+) -> TokenStream2 {
+    if !enabled(config) {
+        return TokenStream2::new();
+    }
+
+    let keys: Vec<&str> = fields.iter().map(|field| field.key).collect();
+    let idents: Vec<&Ident2> = fields.iter().map(|field| field.ident).collect();
+    let readers: Vec<TokenStream2> = fields
+        .iter()
+        .map(|field| match field.optional {
+            false => quote!(::static_toml::runtime::field),
+            true => quote!(::static_toml::runtime::optional_field)
+        })
+        .collect();
+
+    // Fields may be deprecated via `attr`, overriding them is not a use of them
+    quote! {
+        #[allow(deprecated)]
+        impl ::static_toml::runtime::Layer for #type_ident {
+            fn from_toml(
+                value: ::static_toml::runtime::toml::Value,
+                path: &str
+            ) -> std::result::Result<Self, ::static_toml::runtime::Error> {
+                let mut table = ::static_toml::runtime::table(value, path)?;
+                let value = #type_ident {
+                    #(#idents: #readers(&mut table, #keys, path)?),*
+                };
+                ::static_toml::runtime::deny_unknown(table, path)?;
+                std::result::Result::Ok(value)
+            }
+
+            fn merge(
+                &mut self,
+                value: ::static_toml::runtime::toml::Value,
+                path: &str
+            ) -> std::result::Result<(), ::static_toml::runtime::Error> {
+                for (key, value) in ::static_toml::runtime::table(value, path)? {
+                    let path = ::static_toml::runtime::child_path(path, &key);
+                    match key.as_str() {
+                        #(#keys => ::static_toml::runtime::Layer::merge(
+                            &mut self.#idents,
+                            value,
+                            &path
+                        )?,)*
+                        _ => {
+                            return std::result::Result::Err(
+                                ::static_toml::runtime::Error::UnknownKey(path)
+                            )
+                        }
+                    }
+                }
+                std::result::Result::Ok(())
+            }
+
+            fn merge_env(&mut self, name: &str) -> std::result::Result<(), ::static_toml::runtime::Error> {
+                #(::static_toml::runtime::Layer::merge_env(
+                    &mut self.#idents,
+                    &::static_toml::runtime::env_name(name, #keys)
+                )?;)*
+                std::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// Generates the `Layer` implementation for a tuple struct representing an
+/// array.
+///
+/// Tuples are replaced as a whole and need the same length.
+pub(crate) fn tuple(
+    type_ident: &Ident2,
+    len: usize,
+    config: &StaticTomlAttributes
+) -> TokenStream2 {
+    if !enabled(config) {
+        return TokenStream2::new();
+    }
+
+    let indices: Vec<Index> = (0..len).map(Index::from).collect();
+    let values: Vec<Ident2> = (0..len).map(|i| format_ident!("value{i}")).collect();
+    quote! {
+        impl ::static_toml::runtime::Layer for #type_ident {
+            fn from_toml(
+                value: ::static_toml::runtime::toml::Value,
+                path: &str
+            ) -> std::result::Result<Self, ::static_toml::runtime::Error> {
+                let [#(#values),*] = ::static_toml::runtime::tuple::<#len>(value, path)?;
+                std::result::Result::Ok(#type_ident(#(::static_toml::runtime::Layer::from_toml(
+                    #values,
+                    &::static_toml::runtime::child_path(path, #indices)
+                )?),*))
+            }
+
+            fn merge_env(&mut self, name: &str) -> std::result::Result<(), ::static_toml::runtime::Error> {
+                #(::static_toml::runtime::Layer::merge_env(
+                    &mut self.#indices,
+                    &::static_toml::runtime::env_name(name, #indices)
+                )?;)*
+                std::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// Generates the `Layer` implementation for an enum of `mixed_arrays = enum`.
+///
+/// The variants are tried in order, the first one accepting the value is used.
+pub(crate) fn enumeration(
+    type_ident: &Ident2,
+    variants: &[(Ident2, &str)],
+    config: &StaticTomlAttributes
+) -> TokenStream2 {
+    if !enabled(config) {
+        return TokenStream2::new();
+    }
+
+    let variant_idents: Vec<&Ident2> = variants.iter().map(|(ident, _)| ident).collect();
+    let mut kinds: Vec<&str> = Vec::with_capacity(variants.len());
+    for (_, kind) in variants {
+        if !kinds.contains(kind) {
+            kinds.push(kind);
+        }
+    }
+    let expected = kinds.join(" or ");
+    quote! {
+        impl ::static_toml::runtime::Layer for #type_ident {
+            fn from_toml(
+                value: ::static_toml::runtime::toml::Value,
+                path: &str
+            ) -> std::result::Result<Self, ::static_toml::runtime::Error> {
+                #(
+                    let value = match ::static_toml::runtime::Layer::from_toml(value.clone(), path) {
+                        std::result::Result::Ok(inner) => {
+                            return std::result::Result::Ok(#type_ident::#variant_idents(inner))
+                        }
+                        std::result::Result::Err(_) => value
+                    };
+                )*
+                std::result::Result::Err(::static_toml::runtime::type_mismatch(&value, path, #expected))
+            }
+
+            fn merge_env(&mut self, name: &str) -> std::result::Result<(), ::static_toml::runtime::Error> {
+                match self {
+                    #(#type_ident::#variant_idents(inner) => {
+                        ::static_toml::runtime::Layer::merge_env(inner, name)
+                    })*
+                }
+            }
+        }
+    }
+}
+
+/// Generates the `Layer` implementation for a tuple struct representing a
+/// map.
+///
+/// Existing entries are merged, new entries are added.
+/// Maps using `phf` are immutable and cannot be overridden.
+pub(crate) fn map(
+    type_ident: &Ident2,
+    backend: MapBackend,
+    config: &StaticTomlAttributes
+) -> TokenStream2 {
+    if !enabled(config) {
+        return TokenStream2::new();
+    }
+
+    let (from_toml, merge, merge_env) = match backend {
+        MapBackend::Sorted => (
+            quote! {
+                ::static_toml::runtime::map(value, path).map(#type_ident)
+            },
+            quote! {
+                ::static_toml::runtime::merge_map(&mut self.0, value, path)
+            },
+            quote! {
+                ::static_toml::runtime::merge_map_env(&mut self.0, name)
+            }
+        ),
+        #[cfg(feature = "phf")]
+        MapBackend::Phf => (
+            quote! {
+                std::result::Result::Err(::static_toml::runtime::Error::Unsupported(path.to_string()))
+            },
+            quote! {
+                std::result::Result::Err(::static_toml::runtime::Error::Unsupported(path.to_string()))
+            },
+            quote! {
+                let _ = name;
+                std::result::Result::Ok(())
+            }
+        )
+    };
+
+    quote! {
+        impl ::static_toml::runtime::Layer for #type_ident {
+            fn from_toml(
+                value: ::static_toml::runtime::toml::Value,
+                path: &str
+            ) -> std::result::Result<Self, ::static_toml::runtime::Error> {
+                #from_toml
+            }
+
+            fn merge(
+                &mut self,
+                value: ::static_toml::runtime::toml::Value,
+                path: &str
+            ) -> std::result::Result<(), ::static_toml::runtime::Error> {
+                #merge
+            }
+
+            fn merge_env(&mut self, name: &str) -> std::result::Result<(), ::static_toml::runtime::Error> {
+                #merge_env
+            }
+        }
+    }
+}
+
+/// Generates the `load_with_overrides` and `from_env` functions of the root
+/// type.
+///
+/// The embedded value is constructed from the static tokens, so the functions
+/// use the same absolute paths as the static value and have to be placed next
+/// to it.
+pub(crate) fn load(
+    root_type: TokenStream2,
+    static_tokens: &TokenStream2,
+    config: &StaticTomlAttributes
+) -> TokenStream2 {
+    if !enabled(config) {
+        return TokenStream2::new();
+    }
+
+    quote! {
+        #[allow(deprecated)]
+        impl #root_type {
+            /// Loads the TOML file at the path, keys missing in the file keep
+            /// their embedded values.
+            ///
+            /// Keys unknown to the embedded data and values of other types are
+            /// an error.
+            pub fn load_with_overrides(
+                path: impl std::convert::AsRef<std::path::Path>
+            ) -> std::result::Result<Self, ::static_toml::runtime::Error> {
+                ::static_toml::runtime::load(#static_tokens, path.as_ref())
+            }
+
+            /// Overrides the embedded leaf values from environment variables
+            /// named after the prefix and their path, like
+            /// `APP_DATABASE_PORTS_0` for the prefix `APP`.
+            ///
+            /// Values that cannot be parsed into their data type are an error.
+            pub fn from_env(prefix: &str) -> std::result::Result<Self, ::static_toml::runtime::Error> {
+                ::static_toml::runtime::from_env(#static_tokens, prefix)
+            }
+        }
+    }
+}
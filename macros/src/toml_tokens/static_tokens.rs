@@ -0,0 +1,337 @@
+//! Generates Rust tokens for representing static data derived from TOML.
+//!
+//! The `static_tokens` submodule specializes in generating Rust tokens that
+//! represent TOML data as static data structures. This allows for creating
+//! compile-time representations of the data in TOML files.
+
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::Ident as Ident2;
+use toml::value::Array;
+use toml::{Table, Value};
+
+#[cfg(feature = "phf")]
+use crate::parse::MapBackend;
+use crate::parse::{MixedArrays, StaticTomlAttributes};
+use crate::toml_tokens::{CollectAll, PathSegment, TomlTokens, UnifiedKind, UnifiedTable};
+
+/// Generates the Rust tokens for a TOML array.
+///
+/// Returns a TokenStream2 representing the Rust code generated for the array.
+#[inline]
+pub(crate) fn array(
+    array: &Array,
+    key: &str,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    namespace: &mut Vec<Ident2>,
+    namespace_ts: TokenStream2
+) -> Result<TokenStream2, super::super::TomlError> {
+    // Check if slices should be used
+    let use_slices = super::use_slices(array, config);
+    let values_ident = [config
+        .values_ident
+        .as_ref()
+        .map(Ident2::to_string)
+        .unwrap_or_else(|| String::from("values"))];
+
+    // Mixed arrays represented as enums wrap every element in its variant
+    if !use_slices && config.mixed_arrays == Some(MixedArrays::Enum) {
+        let (variants, indices) = super::enum_variants(array);
+        let values_mod_ident = format_ident!("{}", values_ident[0].to_case(Case::Snake));
+        let values_type_ident =
+            super::fixed_ident(&values_ident[0], &config.prefix, &config.suffix);
+
+        namespace.push(values_mod_ident.clone());
+        let inner = array
+            .iter()
+            .zip(indices)
+            .enumerate()
+            .map(|(item, (v, i))| {
+                let (k, _) = &variants[i];
+                let variant_ident = format_ident!("{}", k.to_case(Case::Pascal));
+                namespace.push(format_ident!("{}", k.to_case(Case::Snake)));
+                path.push(PathSegment::Variant(item));
+                let value = v.static_tokens(k, path, config, namespace);
+                path.pop();
+                namespace.pop();
+                let value = value?;
+                Ok(quote!(#namespace_ts::#values_mod_ident::#values_type_ident::#variant_ident(#value)))
+            })
+            .collect_all();
+        namespace.pop();
+        let inner = inner?;
+
+        return Ok(match config.cow {
+            None => quote!(&[#(#inner),*]),
+            Some(_) => quote!(std::borrow::Cow::Borrowed(&[#(#inner),*]))
+        });
+    }
+
+    let key_iter: Box<dyn Iterator<Item = String>> = match use_slices {
+        true => Box::new(values_ident.iter().cycle().cloned()),
+        false => Box::new(
+            values_ident
+                .iter()
+                .cycle()
+                .enumerate()
+                .map(|(i, v)| format!("{v}{i}"))
+        )
+    };
+
+    // Tables with differing keys may share a unified type
+    let unified = match use_slices {
+        true => config.unify_tables.and_then(|_| super::unify_array(array)),
+        false => None
+    };
+
+    // Generate the inner token streams for the array elements
+    let inner: Vec<TokenStream2> = array
+        .iter()
+        .zip(key_iter)
+        .enumerate()
+        .map(|(i, (v, k))| {
+            namespace.push(format_ident!("{}", k.to_case(Case::Snake)));
+            path.push(match use_slices {
+                true => PathSegment::Item(i),
+                false => PathSegment::Index(i)
+            });
+            let value = match (&unified, v) {
+                (Some(unified), Value::Table(table))
+                    if super::user_type(path, config).is_none() =>
+                {
+                    let namespace_ts = quote!(#(#namespace)::*);
+                    unified_table(table, unified, &k, path, config, namespace, namespace_ts)
+                }
+                _ => v.static_tokens(&k, path, config, namespace)
+            };
+            path.pop();
+            namespace.pop();
+            value
+        })
+        .collect_all()?;
+
+    // Generate the final token stream based on whether slices are used or not
+    let type_ident = super::fixed_ident(key, &config.prefix, &config.suffix);
+    // Existing types cannot depend on the lengths of arrays, slices embedded
+    // into them are references
+    Ok(match (use_slices, config.cow) {
+        (true, None) if super::in_user_type(path, config) => quote!(&[#(#inner),*]),
+        (true, None) => quote!([#(#inner),*]),
+        (true, Some(_)) => quote!(std::borrow::Cow::Borrowed(&[#(#inner),*])),
+        (false, _) => quote!(#namespace_ts::#type_ident(#(#inner),*))
+    })
+}
+
+/// Generates the Rust tokens for a TOML table.
+///
+/// Returns a TokenStream2 representing the Rust code generated for the table.
+#[inline]
+pub(crate) fn table(
+    table: &Table,
+    key: &str,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    namespace: &mut Vec<Ident2>,
+    namespace_ts: TokenStream2
+) -> Result<TokenStream2, super::super::TomlError> {
+    // Generate the inner token streams for the table fields
+    let inner: Vec<(Ident2, TokenStream2)> = table
+        .iter()
+        .map(|(k, v)| {
+            let name = super::key_name(k, path, config)?;
+            let field_key = super::snake_ident(&name);
+            namespace.push(field_key.clone());
+            path.push(PathSegment::Key(k.to_string()));
+            let value = (field_key, v.static_tokens(&name, path, config, namespace));
+            path.pop();
+            namespace.pop();
+            match value {
+                (ident, Ok(ts)) => Ok((ident, ts)),
+                (_, Err(e)) => Err(e)
+            }
+        })
+        .collect_all()?;
+
+    // Collect the field keys and values
+    let field_keys: Vec<&Ident2> = inner.iter().map(|(k, _)| k).collect();
+    let field_values: Vec<&TokenStream2> = inner.iter().map(|(_, v)| v).collect();
+
+    // Generate the final token stream for the table, either of the generated
+    // data type or of an existing one
+    let type_path = match super::user_type(path, config) {
+        Some(ty) => {
+            super::check_user_type(table, path, config, ty)?;
+            quote!(#ty)
+        }
+        None => {
+            let type_ident = super::fixed_ident(key, &config.prefix, &config.suffix);
+            quote!(#namespace_ts::#type_ident)
+        }
+    };
+    Ok(quote! {
+        #type_path {
+            #(#field_keys: #field_values),*
+        }
+    })
+}
+
+/// Generates the Rust tokens for a TOML table configured via `map`.
+///
+/// Returns a TokenStream2 representing the entries of the table, either as a
+/// slice sorted by key or, with `map(phf)`, as a `phf::Map`.
+#[inline]
+pub(crate) fn map(
+    table: &Table,
+    key: &str,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    namespace: &mut Vec<Ident2>,
+    namespace_ts: TokenStream2
+) -> Result<TokenStream2, super::super::TomlError> {
+    let unified = super::map_values(table, path, config)?;
+    let values_ident = config
+        .values_ident
+        .as_ref()
+        .map(Ident2::to_string)
+        .unwrap_or_else(|| String::from("values"));
+
+    // Generate the token streams for the values
+    namespace.push(format_ident!("{}", values_ident.to_case(Case::Snake)));
+    let entries = table
+        .iter()
+        .map(|(k, v)| {
+            path.push(PathSegment::Entry(k.to_string()));
+            let value = match (&unified, v) {
+                (Some(unified), Value::Table(table)) => {
+                    let namespace_ts = quote!(#(#namespace)::*);
+                    unified_table(
+                        table,
+                        unified,
+                        &values_ident,
+                        path,
+                        config,
+                        namespace,
+                        namespace_ts
+                    )
+                }
+                _ => v.static_tokens(&values_ident, path, config, namespace)
+            };
+            path.pop();
+            Ok((k.as_str(), value?))
+        })
+        .collect_all();
+    namespace.pop();
+    let entries = entries?;
+
+    let type_ident = super::fixed_ident(key, &config.prefix, &config.suffix);
+    let entries = match super::map_backend(path, config) {
+        #[cfg(feature = "phf")]
+        Some(MapBackend::Phf) => phf_entries(entries),
+        _ => sorted_entries(entries, config)
+    };
+    Ok(quote!(#namespace_ts::#type_ident(#entries)))
+}
+
+/// Generates the entries of a map as a slice sorted by key.
+///
+/// The generated `get` looks up keys via binary search, so the entries are
+/// sorted here instead of relying on the iteration order of the table.
+fn sorted_entries(
+    mut entries: Vec<(&str, TokenStream2)>,
+    config: &StaticTomlAttributes
+) -> TokenStream2 {
+    entries.sort_by_key(|(k, _)| *k);
+    let (keys, values): (Vec<&str>, Vec<TokenStream2>) = entries.into_iter().unzip();
+    match config.cow {
+        None => quote!(&[#((#keys, #values)),*]),
+        Some(_) => quote! {
+            std::borrow::Cow::Borrowed(&[#((std::borrow::Cow::Borrowed(#keys), #values)),*])
+        }
+    }
+}
+
+/// Generates the entries of a map as a `phf::Map`.
+///
+/// The perfect hash function is generated at compile time, the same way the
+/// `phf_map!` macro does.
+#[cfg(feature = "phf")]
+fn phf_entries(entries: Vec<(&str, TokenStream2)>) -> TokenStream2 {
+    let keys: Vec<&str> = entries.iter().map(|(k, _)| *k).collect();
+    let state = phf_generator::generate_hash(&keys);
+
+    let hash_key = state.key;
+    let disps = state.disps.iter().map(|(d1, d2)| quote!((#d1, #d2)));
+    let entries = state.map.iter().map(|&i| {
+        let (key, value) = &entries[i];
+        quote!((#key, #value))
+    });
+
+    quote! {
+        ::static_toml::phf::Map {
+            key: #hash_key,
+            disps: &[#(#disps),*],
+            entries: &[#(#entries),*]
+        }
+    }
+}
+
+/// Generates the Rust tokens for a TOML table with a type unified via
+/// `unify_tables`.
+///
+/// Fields of the unified type that are optional are wrapped in `Some`, or set
+/// to `None` if they are missing in this table.
+pub(crate) fn unified_table(
+    table: &Table,
+    unified: &UnifiedTable,
+    key: &str,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    namespace: &mut Vec<Ident2>,
+    namespace_ts: TokenStream2
+) -> Result<TokenStream2, super::super::TomlError> {
+    // Generate the inner token streams for the fields of the unified type
+    let inner: Vec<(Ident2, TokenStream2)> = unified
+        .fields
+        .iter()
+        .map(|(k, field)| {
+            let name = super::key_name(k, path, config)?;
+            let field_key = super::snake_ident(&name);
+            let Some(value) = table.get(*k)
+            else {
+                return Ok((field_key, quote!(std::option::Option::None)));
+            };
+
+            namespace.push(field_key.clone());
+            path.push(PathSegment::Key(k.to_string()));
+            let value = match (&field.kind, value) {
+                (UnifiedKind::Table(nested), Value::Table(value)) => {
+                    let namespace_ts = quote!(#(#namespace)::*);
+                    unified_table(value, nested, &name, path, config, namespace, namespace_ts)
+                }
+                (_, value) => value.static_tokens(&name, path, config, namespace)
+            };
+            path.pop();
+            namespace.pop();
+
+            let value = value?;
+            match field.optional {
+                false => Ok((field_key, value)),
+                true => Ok((field_key, quote!(std::option::Option::Some(#value))))
+            }
+        })
+        .collect_all()?;
+
+    // Collect the field keys and values
+    let field_keys: Vec<&Ident2> = inner.iter().map(|(k, _)| k).collect();
+    let field_values: Vec<&TokenStream2> = inner.iter().map(|(_, v)| v).collect();
+
+    // Generate the final token stream for the table
+    let type_ident = super::fixed_ident(key, &config.prefix, &config.suffix);
+    Ok(quote! {
+        #namespace_ts::#type_ident {
+            #(#field_keys: #field_values),*
+        }
+    })
+}
@@ -0,0 +1,350 @@
+use std::collections::BTreeSet;
+
+use quote::{format_ident, quote};
+use syn::parse_quote;
+use toml::value::Value;
+
+use crate::parse::{MapBackend, MapTable, StaticTomlAttributes};
+use crate::toml_tokens::{default_impls, interpolate_values, path_string, skip_values, TomlTokens};
+use crate::TomlError;
+
+mod static_tokens;
+mod type_tokens;
+
+#[test]
+fn type_eq_works() {
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+
+    let servers = toml.get("servers").unwrap();
+    let alpha = servers.get("alpha").unwrap();
+    let beta = servers.get("beta").unwrap();
+    assert!(alpha.type_eq(beta));
+
+    let database = toml.get("database").unwrap();
+    let ports = database.get("ports").unwrap();
+    assert!(ports[0].type_eq(&ports[1]));
+    assert!(ports[1].type_eq(&ports[2]));
+
+    let data = database.get("data").unwrap();
+    assert!(!data[0].type_eq(&data[1]));
+}
+
+#[test]
+fn datetime_type_eq_works() {
+    let datetimes: Value = toml::from_str(
+        "
+    offset = [1979-05-27T07:32:00Z, 1979-05-27T00:32:00-07:00]
+    local = [1979-05-27T07:32:00, 1979-05-27]
+    "
+    )
+    .unwrap();
+    let offset = datetimes.get("offset").unwrap();
+    assert!(offset[0].type_eq(&offset[1]));
+    let local = datetimes.get("local").unwrap();
+    assert!(!local[0].type_eq(&local[1]));
+}
+
+#[test]
+fn ident_validator_works() {
+    let toml: Value = toml::from_str("123_key = 123").unwrap();
+    let config = StaticTomlAttributes::default();
+    let expected = "123_key".to_string();
+
+    let type_tokens_res = toml.type_tokens("key", &mut Vec::new(), &config, quote!(), &[]);
+    let Err(TomlError::KeyInvalid(key, _)) = type_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    assert_eq!(key, expected);
+
+    let static_tokens_res = toml.static_tokens("key", &mut Vec::new(), &config, &mut Vec::new());
+    let Err(TomlError::KeyInvalid(key, _)) = static_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    assert_eq!(key, expected);
+}
+
+#[test]
+fn numeric_range_check_works() {
+    let toml: Value = toml::from_str("[database]\nports = [8000, 70000]").unwrap();
+    let config = StaticTomlAttributes {
+        types: vec![parse_quote!(database.ports = [u16])],
+        ..StaticTomlAttributes::default()
+    };
+
+    let static_tokens_res = toml.static_tokens("key", &mut Vec::new(), &config, &mut Vec::new());
+    let Err(TomlError::ValueOutOfRange { path, value, ty }) = static_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    assert_eq!(path_string(&path), "database.ports.1");
+    assert_eq!(value, "70000");
+    assert_eq!(ty, "u16");
+
+    let config = StaticTomlAttributes {
+        types: vec![parse_quote!(database.ports = u16)],
+        ..StaticTomlAttributes::default()
+    };
+    let type_tokens_res = toml.type_tokens("key", &mut Vec::new(), &config, quote!(), &[]);
+    let Err(TomlError::TypeOverrideInvalid { path, ty, found }) = type_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    assert_eq!(path_string(&path), "database.ports");
+    assert_eq!(ty, "u16");
+    assert_eq!(found, "array");
+}
+
+#[test]
+fn map_validation_works() {
+    let toml: Value = toml::from_str("[limits]\na = 1\nb = \"two\"").unwrap();
+    let config = StaticTomlAttributes {
+        maps: vec![MapTable {
+            path: parse_quote!(limits),
+            backend: MapBackend::Sorted
+        }],
+        ..StaticTomlAttributes::default()
+    };
+
+    let type_tokens_res = toml.type_tokens("key", &mut Vec::new(), &config, quote!(), &[]);
+    let Err(TomlError::MapInvalid(path)) = type_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    assert_eq!(path_string(&path), "limits");
+}
+
+#[test]
+fn errors_are_collected() {
+    let toml: Value = toml::from_str("1st = 1\n[table]\n2nd = 2\nvalid = 3\n\"3rd\" = 4").unwrap();
+    let config = StaticTomlAttributes::default();
+
+    let static_tokens_res = toml.static_tokens("key", &mut Vec::new(), &config, &mut Vec::new());
+    let Err(TomlError::Multiple(errors)) = static_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    let keys: Vec<String> = errors
+        .into_iter()
+        .map(|e| match e {
+            TomlError::KeyInvalid(key, path) => format!("{key} at {}", path_string(&path)),
+            e => panic!("unexpected error {e:?}")
+        })
+        .collect();
+    assert_eq!(keys, ["1st at 1st", "2nd at table.2nd", "3rd at table.3rd"]);
+}
+
+#[test]
+fn type_errors_are_collected() {
+    let toml: Value =
+        toml::from_str("\"1a\" = 3\nfooBar = 1\nfoo_bar = 2\n[t]\n\"2b\" = 2").unwrap();
+    let configs = [StaticTomlAttributes::default(), StaticTomlAttributes {
+        shared: vec![parse_quote!(t = Shared)],
+        ..StaticTomlAttributes::default()
+    }];
+
+    for config in configs {
+        let type_tokens_res = toml.type_tokens("key", &mut Vec::new(), &config, quote!(), &[]);
+        let Err(TomlError::Multiple(errors)) = type_tokens_res
+        else {
+            panic!("unexpected type");
+        };
+        let errors: Vec<String> = errors
+            .into_iter()
+            .map(|e| match e {
+                TomlError::KeyInvalid(key, path) => format!("{key} at {}", path_string(&path)),
+                TomlError::KeyCollision { first, second, .. } => format!("{first} {second}"),
+                e => panic!("unexpected error {e:?}")
+            })
+            .collect();
+        assert_eq!(errors, ["1a at 1a", "fooBar foo_bar", "2b at t.2b"]);
+    }
+}
+
+#[test]
+fn collision_detection_works() {
+    let toml: Value = toml::from_str("[table]\nfooBar = 1\nfoo_bar = 2\nFooBar = 3").unwrap();
+    let config = StaticTomlAttributes::default();
+
+    let type_tokens_res = toml.type_tokens("key", &mut Vec::new(), &config, quote!(), &[]);
+    let Err(TomlError::Multiple(errors)) = type_tokens_res
+    else {
+        panic!("unexpected type");
+    };
+    let collisions: Vec<String> = errors
+        .into_iter()
+        .map(|e| match e {
+            TomlError::KeyCollision {
+                path,
+                first,
+                second,
+                ident
+            } => format!("{first} {second} {ident} {}", path_string(&path)),
+            e => panic!("unexpected error {e:?}")
+        })
+        .collect();
+    assert_eq!(collisions, [
+        "FooBar fooBar foo_bar table.fooBar",
+        "FooBar foo_bar foo_bar table.foo_bar"
+    ]);
+}
+
+#[test]
+fn skip_values_works() {
+    let mut toml: Value = toml::from_str(
+        r#"
+    name = "static-toml"
+
+    [tool.other]
+    "not an ident" = true
+
+    [meta]
+
+    [[servers]]
+    name = "alpha"
+    internal = 1
+    "#
+    )
+    .unwrap();
+    let config = StaticTomlAttributes {
+        skips: vec![
+            parse_quote!(tool.other),
+            parse_quote!(servers.*.internal),
+            parse_quote!(missing),
+        ],
+        ..StaticTomlAttributes::default()
+    };
+
+    let mut matched = vec![false; config.skips.len()];
+    skip_values(&mut toml, &mut Vec::new(), &config, &mut matched);
+    assert_eq!(matched, [true, true, false]);
+
+    // `tool` is left empty and removed, while `meta` was empty before
+    let expected: Value = toml::from_str(
+        r#"
+    name = "static-toml"
+
+    [meta]
+
+    [[servers]]
+    name = "alpha"
+    "#
+    )
+    .unwrap();
+    assert_eq!(toml, expected);
+}
+
+#[test]
+fn default_impls_works() {
+    let toml: Value = toml::from_str(
+        r#"
+    pair = [1, "two"]
+
+    [database]
+    port = 8000
+
+    [[servers]]
+    name = "alpha"
+
+    [sizes.small]
+    width = 10
+    "#
+    )
+    .unwrap();
+    let config = StaticTomlAttributes {
+        maps: vec![MapTable {
+            path: parse_quote!(sizes),
+            backend: MapBackend::Sorted
+        }],
+        ..StaticTomlAttributes::default()
+    };
+
+    let impls = default_impls(&toml, "toml", &mut Vec::new(), &config, &mut vec![
+        format_ident!("toml"),
+    ])
+    .unwrap();
+    // Items of slices and values of maps share their type, so they get no impl
+    let targets = [
+        quote!(toml::Toml),
+        quote!(toml::database::Database),
+        quote!(toml::pair::Pair),
+        quote!(toml::sizes::Sizes)
+    ];
+    assert_eq!(impls.len(), targets.len());
+    for (ts, target) in impls.iter().zip(targets) {
+        let prefix = quote! {
+            #[allow(deprecated)]
+            impl std::default::Default for #target
+        }
+        .to_string();
+        assert!(ts.to_string().starts_with(&prefix));
+    }
+
+    let database_expected = quote! {
+        #[allow(deprecated)]
+        impl std::default::Default for toml::database::Database {
+            fn default() -> Self {
+                toml::database::Database {
+                    port: 8000i64
+                }
+            }
+        }
+    };
+    assert_eq!(impls[1].to_string(), database_expected.to_string());
+}
+
+#[test]
+fn interpolate_values_works() {
+    std::env::set_var("STATIC_TOML_TEST_HOST", "example.com");
+    std::env::set_var("STATIC_TOML_TEST_EMPTY", "");
+    std::env::remove_var("STATIC_TOML_TEST_UNSET");
+
+    let mut toml: Value = toml::from_str(
+        r#"
+    url = "https://${STATIC_TOML_TEST_HOST}/v1"
+    fallbacks = ["${STATIC_TOML_TEST_UNSET:-localhost}", "${STATIC_TOML_TEST_EMPTY:-empty}"]
+    literal = "$${STATIC_TOML_TEST_HOST} costs $5"
+    port = 8000
+    "#
+    )
+    .unwrap();
+    let mut vars = BTreeSet::new();
+    interpolate_values(&mut toml, &mut Vec::new(), &mut vars).unwrap();
+    let expected: Value = toml::from_str(
+        r#"
+    url = "https://example.com/v1"
+    fallbacks = ["localhost", "empty"]
+    literal = "${STATIC_TOML_TEST_HOST} costs $5"
+    port = 8000
+    "#
+    )
+    .unwrap();
+    assert_eq!(toml, expected);
+    assert_eq!(Vec::from_iter(vars), [
+        "STATIC_TOML_TEST_EMPTY",
+        "STATIC_TOML_TEST_HOST",
+        "STATIC_TOML_TEST_UNSET"
+    ]);
+
+    let mut toml: Value = toml::from_str(
+        r#"
+    missing = "${STATIC_TOML_TEST_UNSET}"
+    unterminated = "${STATIC_TOML_TEST_HOST"
+    "#
+    )
+    .unwrap();
+    let errors = interpolate_values(&mut toml, &mut Vec::new(), &mut BTreeSet::new())
+        .unwrap_err()
+        .into_errors();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        TomlError::EnvVarMissing { path, name }
+            if path_string(path) == "missing" && name == "STATIC_TOML_TEST_UNSET"
+    ));
+    assert!(matches!(
+        &errors[1],
+        TomlError::InterpolationInvalid { path, .. } if path_string(path) == "unterminated"
+    ));
+}
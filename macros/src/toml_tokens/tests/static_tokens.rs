@@ -0,0 +1,457 @@
+use quote::{format_ident, quote};
+use syn::parse_quote;
+use toml::Value;
+
+use crate::parse::{KeyFallback, MapBackend, MapTable, MixedArrays, StaticTomlAttributes};
+use crate::toml_tokens::{path_string, TomlTokens};
+use crate::TomlError;
+
+#[test]
+fn default_static_tokens_works() {
+    let config = StaticTomlAttributes::default();
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            database: toml::database::Database {
+                data: toml::database::data::Data(["delta", "phi"], [3.14f64]),
+                enabled: true,
+                ports: [8000i64, 8001i64, 8002i64],
+                temp_targets: toml::database::temp_targets::TempTargets {
+                    case: 72f64,
+                    cpu: 79.5f64
+                }
+            },
+            owner: toml::owner::Owner {
+                dob: ::static_toml::datetime::OffsetDatetime::new(
+                    ::static_toml::datetime::LocalDate::new(1979u16, 5u8, 27u8),
+                    ::static_toml::datetime::LocalTime::new(7u8, 32u8, 0u8, 0u32),
+                    ::static_toml::datetime::Offset::custom(-480i16)
+                ),
+                name: "Tom Preston-Werner"
+            },
+            servers: toml::servers::Servers {
+                alpha: toml::servers::alpha::Alpha {
+                    ip: "10.0.0.1",
+                    role: "frontend"
+                },
+                beta: toml::servers::beta::Beta {
+                    ip: "10.0.0.2",
+                    role: "backend"
+                }
+            },
+            title: "TOML Example"
+        }
+    };
+
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn values_ident_works() {
+    let default_config = StaticTomlAttributes::default();
+    let value_ident_config = StaticTomlAttributes {
+        values_ident: Some(format_ident!("items")),
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        "
+    [[list]]
+    value = 123
+
+    [[list]]
+    value = 456
+
+    [[tuple]]
+    a = 1
+
+    [[tuple]]
+    b = 2
+    "
+    )
+    .unwrap();
+
+    let default_toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &default_config,
+            &mut namespace
+        )
+        .unwrap();
+    let default_toml_ts_expected = quote! {
+        toml::Toml {
+            list: [
+                toml::list::values::Values { value: 123i64 },
+                toml::list::values::Values { value: 456i64 }
+            ],
+            tuple: toml::tuple::Tuple(
+                toml::tuple::values_0::Values0 { a: 1i64 },
+                toml::tuple::values_1::Values1 { b: 2i64 }
+            )
+        }
+    };
+    assert_eq!(
+        default_toml_ts.to_string(),
+        default_toml_ts_expected.to_string()
+    );
+
+    let items_toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &value_ident_config,
+            &mut namespace
+        )
+        .unwrap();
+    let items_toml_ts_expected = quote! {
+        toml::Toml {
+            list: [
+                toml::list::items::Items { value: 123i64 },
+                toml::list::items::Items { value: 456i64 }
+            ],
+            tuple: toml::tuple::Tuple(
+                toml::tuple::items_0::Items0 { a: 1i64 },
+                toml::tuple::items_1::Items1 { b: 2i64 }
+            )
+        }
+    };
+    assert_eq!(
+        items_toml_ts.to_string(),
+        items_toml_ts_expected.to_string()
+    );
+}
+
+#[test]
+fn mixed_arrays_enum_works() {
+    let config = StaticTomlAttributes {
+        mixed_arrays: Some(MixedArrays::Enum),
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value =
+        toml::from_str(r#"data = [["delta", "phi"], "text", [3.14], "more"]"#).unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            data: &[
+                toml::data::values::Values::Array0(["delta", "phi"]),
+                toml::data::values::Values::String("text"),
+                toml::data::values::Values::Array1([3.14f64]),
+                toml::data::values::Values::String("more")
+            ]
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn unify_tables_works() {
+    let config = StaticTomlAttributes {
+        unify_tables: Some(()),
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        r#"
+    [[plugins]]
+    name = "fmt"
+
+    [[plugins]]
+    name = "lint"
+    args = ["--strict"]
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            plugins: [
+                toml::plugins::values::Values {
+                    args: std::option::Option::None,
+                    name: "fmt"
+                },
+                toml::plugins::values::Values {
+                    args: std::option::Option::Some(["--strict"]),
+                    name: "lint"
+                }
+            ]
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn numeric_types_work() {
+    let config = StaticTomlAttributes {
+        int: Some(format_ident!("u32")),
+        types: vec![
+            parse_quote!(database.ports = [u16]),
+            parse_quote!(database.temp_targets.* = f32),
+        ],
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        "
+    limit = 42
+
+    [database]
+    ports = [8000, 8001]
+    temp_targets = { cpu = 79.5, case = 72 }
+    "
+    )
+    .unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            database: toml::database::Database {
+                ports: [8000u16, 8001u16],
+                temp_targets: toml::database::temp_targets::TempTargets {
+                    case: 72f32,
+                    cpu: 79.5f32
+                }
+            },
+            limit: 42u32
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn map_works() {
+    let config = StaticTomlAttributes {
+        maps: vec![MapTable {
+            path: parse_quote!(servers),
+            backend: MapBackend::Sorted
+        }],
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.beta]
+    ip = "10.0.0.2"
+
+    [servers.alpha]
+    ip = "10.0.0.1"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            servers: toml::servers::Servers(&[
+                ("alpha", toml::servers::values::Values { ip: "10.0.0.1" }),
+                ("beta", toml::servers::values::Values { ip: "10.0.0.2" })
+            ])
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn key_names_work() {
+    let config = StaticTomlAttributes {
+        renames: vec![parse_quote!("1st" = first)],
+        key_fallback: Some(KeyFallback::Transliterate),
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        r#"
+    "1st" = 1
+    "2nd" = 2
+    "größe" = 3
+    type = "keyword"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            first: 1i64,
+            _2_nd: 2i64,
+            grosse: 3i64,
+            r#type: "keyword"
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn user_types_work() {
+    let config = StaticTomlAttributes {
+        user_types: vec![
+            parse_quote!(servers.* = crate::ServerConfig),
+            parse_quote!(servers.*.tls = Tls),
+        ],
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.alpha]
+    ip = "10.0.0.1"
+    ports = [8000, 8001]
+    tls = { cert = "alpha.pem" }
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap();
+    let toml_ts_expected = quote! {
+        toml::Toml {
+            servers: toml::servers::Servers {
+                alpha: crate::ServerConfig {
+                    ip: "10.0.0.1",
+                    ports: &[8000i64, 8001i64],
+                    tls: Tls { cert: "alpha.pem" }
+                }
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.alpha]
+    ip = "10.0.0.1"
+    limits = { requests = 100 }
+    "#
+    )
+    .unwrap();
+    let error = toml
+        .static_tokens("toml", &mut Vec::new(), &config, &mut namespace)
+        .unwrap_err();
+    let TomlError::UserTypeInvalid { path, ty, .. } = error
+    else {
+        panic!("expected an invalid user type, got {error:?}");
+    };
+    assert_eq!(path_string(&path), "servers.alpha.limits");
+    assert_eq!(ty, "crate::ServerConfig");
+
+    // Keys without a field are reported if the fields of the type are known
+    let config = StaticTomlAttributes {
+        user_type_fields: [("ServerConfig".to_string(), vec!["ip".to_string()])].into(),
+        ..config
+    };
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.alpha]
+    ip = "10.0.0.1"
+    hostName = "alpha"
+    "#
+    )
+    .unwrap();
+    let error = toml
+        .static_tokens("toml", &mut Vec::new(), &config, &mut namespace)
+        .unwrap_err();
+    let TomlError::UserTypeInvalid { path, ty, reason } = error
+    else {
+        panic!("expected an invalid user type, got {error:?}");
+    };
+    assert_eq!(path_string(&path), "servers.alpha.hostName");
+    assert_eq!(ty, "crate::ServerConfig");
+    assert_eq!(reason, "it has no such field");
+}
+
+#[test]
+#[cfg(feature = "phf")]
+fn phf_map_works() {
+    let config = StaticTomlAttributes {
+        maps: vec![MapTable {
+            path: parse_quote!(servers),
+            backend: MapBackend::Phf
+        }],
+        ..StaticTomlAttributes::default()
+    };
+    let mut namespace = vec![format_ident!("toml")];
+
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.beta]
+    ip = "10.0.0.2"
+
+    [servers.alpha]
+    ip = "10.0.0.1"
+    "#
+    )
+    .unwrap();
+
+    // The entries are in the order of their hashes
+    let toml_ts = toml
+        .static_tokens(
+            namespace[0].to_string().as_str(),
+            &mut Vec::new(),
+            &config,
+            &mut namespace
+        )
+        .unwrap()
+        .to_string();
+    assert!(toml_ts.contains(&quote!(::static_toml::phf::Map).to_string()));
+    for entry in [
+        quote!(("alpha", toml::servers::values::Values { ip: "10.0.0.1" })),
+        quote!(("beta", toml::servers::values::Values { ip: "10.0.0.2" }))
+    ] {
+        assert!(toml_ts.contains(&entry.to_string()));
+    }
+}
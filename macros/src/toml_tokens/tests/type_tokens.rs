@@ -0,0 +1,1281 @@
+use proc_macro2::Span as Span2;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, LitBool};
+use toml::value::Value;
+
+use crate::parse::{KeyFallback, Layout, MapBackend, MapTable, MixedArrays, StaticTomlAttributes};
+use crate::toml_tokens::{path_string, TomlTokens};
+use crate::TomlError;
+
+#[test]
+fn default_type_tokens_works() {
+    let config = StaticTomlAttributes::default();
+    let empty_derive = vec![];
+
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+    let title = toml.get("title").unwrap();
+    let database = toml.get("database").unwrap();
+    let enabled = database.get("enabled").unwrap();
+    let ports = database.get("ports").unwrap();
+    let data = database.get("data").unwrap();
+    let temp_targets = database.get("temp_targets").unwrap();
+
+    let title_ts = title
+        .type_tokens(
+            "title",
+            &mut Vec::new(),
+            &config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let title_ts_expected = quote! {
+        pub mod title {
+            pub type Title = &'static str;
+        }
+    };
+    assert_eq!(title_ts.to_string(), title_ts_expected.to_string());
+
+    let enabled_ts = enabled
+        .type_tokens(
+            "enabled",
+            &mut Vec::new(),
+            &config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let enabled_ts_expected = quote! {
+        pub mod enabled {
+            pub type Enabled = bool;
+        }
+    };
+    assert_eq!(enabled_ts.to_string(), enabled_ts_expected.to_string());
+
+    let ports_ts = ports
+        .type_tokens(
+            "ports",
+            &mut Vec::new(),
+            &config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let ports_ts_expected = quote! {
+        pub mod ports {
+            pub type Ports = [values::Values; 3usize];
+
+            pub mod values {
+                pub type Values = i64;
+            }
+        }
+    };
+    assert_eq!(ports_ts.to_string(), ports_ts_expected.to_string());
+
+    let data_ts = data
+        .type_tokens("data", &mut Vec::new(), &config, quote!(pub), &empty_derive)
+        .unwrap();
+    let data_ts_expected = quote! {
+        pub mod data {
+            pub struct Data(pub values_0::Values0, pub values_1::Values1);
+
+            pub mod values_0 {
+                pub type Values0 = [values::Values; 2usize];
+
+                pub mod values {
+                    pub type Values = &'static str;
+                }
+            }
+
+            pub mod values_1 {
+                pub type Values1 = [values::Values; 1usize];
+
+                pub mod values {
+                    pub type Values = f64;
+                }
+            }
+        }
+    };
+    assert_eq!(data_ts.to_string(), data_ts_expected.to_string());
+
+    let temp_targets_ts = temp_targets
+        .type_tokens(
+            "temp_targets",
+            &mut Vec::new(),
+            &config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let temp_targets_ts_expected = quote! {
+        pub mod temp_targets {
+            pub struct TempTargets {
+                pub case: case::Case,
+                pub cpu: cpu::Cpu
+            }
+
+            pub mod case {
+                pub type Case = f64;
+            }
+
+            pub mod cpu {
+                pub type Cpu = f64;
+            }
+        }
+    };
+    assert_eq!(
+        temp_targets_ts.to_string(),
+        temp_targets_ts_expected.to_string()
+    );
+
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &empty_derive)
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                pub database: database::Database,
+                pub owner: owner::Owner,
+                pub servers: servers::Servers,
+                pub title: title::Title
+            }
+
+            pub mod database {
+                pub struct Database {
+                    pub data: data::Data,
+                    pub enabled: enabled::Enabled,
+                    pub ports: ports::Ports,
+                    pub temp_targets: temp_targets::TempTargets
+                }
+
+                pub mod data {
+                    pub struct Data(pub values_0::Values0, pub values_1::Values1);
+
+                    pub mod values_0 {
+                        pub type Values0 = [values::Values; 2usize];
+
+                        pub mod values {
+                            pub type Values = &'static str;
+                        }
+                    }
+
+                    pub mod values_1 {
+                        pub type Values1 = [values::Values; 1usize];
+
+                        pub mod values {
+                            pub type Values = f64;
+                        }
+                    }
+                }
+
+                pub mod enabled {
+                    pub type Enabled = bool;
+                }
+
+                pub mod ports {
+                    pub type Ports = [values::Values; 3usize];
+
+                    pub mod values {
+                        pub type Values = i64;
+                    }
+                }
+
+                pub mod temp_targets {
+                    pub struct TempTargets {
+                        pub case: case::Case,
+                        pub cpu: cpu::Cpu
+                    }
+
+                    pub mod case {
+                        pub type Case = f64;
+                    }
+
+                    pub mod cpu {
+                        pub type Cpu = f64;
+                    }
+                }
+            }
+
+            pub mod owner {
+                pub struct Owner {
+                    pub dob: dob::Dob,
+                    pub name: name::Name
+                }
+
+                pub mod dob {
+                    pub type Dob = ::static_toml::datetime::OffsetDatetime;
+                }
+
+                pub mod name {
+                    pub type Name = &'static str;
+                }
+            }
+
+            pub mod servers {
+                pub struct Servers {
+                    pub alpha: alpha::Alpha,
+                    pub beta: beta::Beta
+                }
+
+                pub mod alpha {
+                    pub struct Alpha {
+                        pub ip: ip::Ip,
+                        pub role: role::Role
+                    }
+
+                    pub mod ip {
+                        pub type Ip = &'static str;
+                    }
+
+                    pub mod role {
+                        pub type Role = &'static str;
+                    }
+                }
+
+                pub mod beta {
+                    pub struct Beta {
+                        pub ip: ip::Ip,
+                        pub role: role::Role
+                    }
+
+                    pub mod ip {
+                        pub type Ip = &'static str;
+                    }
+
+                    pub mod role {
+                        pub type Role = &'static str;
+                    }
+                }
+            }
+
+            pub mod title {
+                pub type Title = &'static str;
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn configured_type_tokens_work() {
+    let values_ident_config = StaticTomlAttributes {
+        values_ident: Some(format_ident!("items")),
+        ..StaticTomlAttributes::default()
+    };
+
+    let prefer_slices_config = StaticTomlAttributes {
+        prefer_slices: Some(LitBool::new(false, Span2::call_site())),
+        ..StaticTomlAttributes::default()
+    };
+
+    let prefix_config = StaticTomlAttributes {
+        prefix: Some(format_ident!("Prefix")),
+        ..StaticTomlAttributes::default()
+    };
+
+    let suffix_config = StaticTomlAttributes {
+        suffix: Some(format_ident!("Suffix")),
+        ..StaticTomlAttributes::default()
+    };
+
+    let prefix_suffix_config = StaticTomlAttributes {
+        prefix: Some(format_ident!("Prefix")),
+        suffix: Some(format_ident!("Suffix")),
+        ..StaticTomlAttributes::default()
+    };
+
+    let cow_config = StaticTomlAttributes {
+        cow: Some(()),
+        ..StaticTomlAttributes::default()
+    };
+
+    let empty_derive = vec![];
+
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+    let title = toml.get("title").unwrap();
+    let database = toml.get("database").unwrap();
+    let ports = database.get("ports").unwrap();
+
+    let values_ident_ts = ports
+        .type_tokens(
+            "ports",
+            &mut Vec::new(),
+            &values_ident_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let values_ident_ts_expected = quote! {
+        pub mod ports {
+            pub type Ports = [items::Items; 3usize];
+
+            pub mod items {
+                pub type Items = i64;
+            }
+        }
+    };
+    assert_eq!(
+        values_ident_ts.to_string(),
+        values_ident_ts_expected.to_string()
+    );
+
+    let prefer_slices_ts = ports
+        .type_tokens(
+            "ports",
+            &mut Vec::new(),
+            &prefer_slices_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let prefer_slices_ts_expected = quote! {
+        pub mod ports {
+            pub struct Ports(pub values_0::Values0, pub values_1::Values1, pub values_2::Values2);
+
+            pub mod values_0 {
+                pub type Values0 = i64;
+            }
+
+            pub mod values_1 {
+                pub type Values1 = i64;
+            }
+
+            pub mod values_2 {
+                pub type Values2 = i64;
+            }
+        }
+    };
+    assert_eq!(
+        prefer_slices_ts.to_string(),
+        prefer_slices_ts_expected.to_string()
+    );
+
+    let prefix_ts = title
+        .type_tokens(
+            "title",
+            &mut Vec::new(),
+            &prefix_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let prefix_ts_expected = quote! {
+        pub mod title {
+            pub type PrefixTitle = &'static str;
+        }
+    };
+    assert_eq!(prefix_ts.to_string(), prefix_ts_expected.to_string());
+
+    let suffix_ts = title
+        .type_tokens(
+            "title",
+            &mut Vec::new(),
+            &suffix_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let suffix_ts_expected = quote! {
+        pub mod title {
+            pub type TitleSuffix = &'static str;
+        }
+    };
+    assert_eq!(suffix_ts.to_string(), suffix_ts_expected.to_string());
+
+    let prefix_suffix_ts = title
+        .type_tokens(
+            "title",
+            &mut Vec::new(),
+            &prefix_suffix_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let prefix_suffix_ts_expected = quote! {
+        pub mod title {
+            pub type PrefixTitleSuffix = &'static str;
+        }
+    };
+    assert_eq!(
+        prefix_suffix_ts.to_string(),
+        prefix_suffix_ts_expected.to_string()
+    );
+
+    let prefix_suffix_ts2 = ports
+        .type_tokens(
+            "ports",
+            &mut Vec::new(),
+            &prefix_suffix_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let prefix_suffix_ts2_expected = quote! {
+        pub mod ports {
+            pub type PrefixPortsSuffix = [values::PrefixValuesSuffix; 3usize];
+
+            pub mod values {
+                pub type PrefixValuesSuffix = i64;
+            }
+        }
+    };
+    assert_eq!(
+        prefix_suffix_ts2.to_string(),
+        prefix_suffix_ts2_expected.to_string()
+    );
+
+    let cow_ts = ports
+        .type_tokens(
+            "ports",
+            &mut Vec::new(),
+            &cow_config,
+            quote!(pub),
+            &empty_derive
+        )
+        .unwrap();
+    let cow_ts_expected = quote! {
+        pub mod ports {
+            pub type Ports = std::borrow::Cow<'static, [values::Values]>;
+
+            pub mod values {
+                pub type Values = i64;
+            }
+        }
+    };
+    assert_eq!(cow_ts.to_string(), cow_ts_expected.to_string())
+}
+
+#[test]
+fn derive_propagation_works() {
+    let config = StaticTomlAttributes::default();
+    let derive: Vec<Attribute> = vec![
+        parse_quote!(#[derive(PartialEq, Eq)]),
+        parse_quote!(#[derive(Default)]),
+    ];
+
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+    let servers = toml.get("servers").unwrap();
+
+    let servers_derived_ts = servers
+        .type_tokens("servers", &mut Vec::new(), &config, quote!(pub), &derive)
+        .unwrap();
+    let servers_derived_ts_expected = quote! {
+        pub mod servers {
+            #[derive(PartialEq, Eq)]
+            #[derive(Default)]
+            pub struct Servers {
+                pub alpha: alpha::Alpha,
+                pub beta: beta::Beta
+            }
+
+            pub mod alpha {
+                #[derive(PartialEq, Eq)]
+                #[derive(Default)]
+                pub struct Alpha {
+                    pub ip: ip::Ip,
+                    pub role: role::Role
+                }
+
+                pub mod ip {
+                    pub type Ip = &'static str;
+                }
+
+                pub mod role {
+                    pub type Role = &'static str;
+                }
+            }
+
+            pub mod beta {
+                #[derive(PartialEq, Eq)]
+                #[derive(Default)]
+                pub struct Beta {
+                    pub ip: ip::Ip,
+                    pub role: role::Role
+                }
+
+                pub mod ip {
+                    pub type Ip = &'static str;
+                }
+
+                pub mod role {
+                    pub type Role = &'static str;
+                }
+            }
+        }
+    };
+    assert_eq!(
+        servers_derived_ts.to_string(),
+        servers_derived_ts_expected.to_string()
+    );
+}
+
+#[test]
+fn mixed_arrays_enum_works() {
+    let config = StaticTomlAttributes {
+        mixed_arrays: Some(MixedArrays::Enum),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value =
+        toml::from_str(r#"data = [["delta", "phi"], [3.14], "text", "more"]"#).unwrap();
+    let data = toml.get("data").unwrap();
+
+    let data_ts = data
+        .type_tokens("data", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let data_ts_expected = quote! {
+        pub mod data {
+            pub type Data = &'static [values::Values];
+
+            pub mod values {
+                pub enum Values {
+                    Array0(array_0::Array0),
+                    Array1(array_1::Array1),
+                    String(string::String)
+                }
+
+                pub mod array_0 {
+                    pub type Array0 = [values::Values; 2usize];
+
+                    pub mod values {
+                        pub type Values = &'static str;
+                    }
+                }
+
+                pub mod array_1 {
+                    pub type Array1 = [values::Values; 1usize];
+
+                    pub mod values {
+                        pub type Values = f64;
+                    }
+                }
+
+                pub mod string {
+                    pub type String = &'static str;
+                }
+            }
+        }
+    };
+    assert_eq!(data_ts.to_string(), data_ts_expected.to_string());
+}
+
+#[test]
+fn unify_tables_works() {
+    let config = StaticTomlAttributes {
+        unify_tables: Some(()),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    [[plugins]]
+    name = "fmt"
+
+    [[plugins]]
+    name = "lint"
+    args = ["--strict"]
+    env = { level = 2 }
+    "#
+    )
+    .unwrap();
+    let plugins = toml.get("plugins").unwrap();
+
+    let plugins_ts = plugins
+        .type_tokens("plugins", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let plugins_ts_expected = quote! {
+        pub mod plugins {
+            pub type Plugins = [values::Values; 2usize];
+
+            pub mod values {
+                pub struct Values {
+                    pub args: std::option::Option<args::Args>,
+                    pub env: std::option::Option<env::Env>,
+                    pub name: name::Name
+                }
+
+                pub mod args {
+                    pub type Args = [values::Values; 1usize];
+
+                    pub mod values {
+                        pub type Values = &'static str;
+                    }
+                }
+
+                pub mod env {
+                    pub struct Env {
+                        pub level: level::Level
+                    }
+
+                    pub mod level {
+                        pub type Level = i64;
+                    }
+                }
+
+                pub mod name {
+                    pub type Name = &'static str;
+                }
+            }
+        }
+    };
+    assert_eq!(plugins_ts.to_string(), plugins_ts_expected.to_string());
+
+    // Values of different types under the same key cannot be unified.
+    let toml: Value = toml::from_str("list = [{ a = 1 }, { a = \"1\" }]").unwrap();
+    let list = toml.get("list").unwrap();
+    let list_ts = list
+        .type_tokens("list", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    assert!(list_ts.to_string().contains("pub struct List"));
+}
+
+#[test]
+fn numeric_types_work() {
+    let config = StaticTomlAttributes {
+        int: Some(format_ident!("u32")),
+        types: vec![
+            parse_quote!(database.ports = [u16]),
+            parse_quote!(database.temp_targets.* = f32),
+        ],
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        "
+    limit = 42
+
+    [database]
+    ports = [8000, 8001]
+    temp_targets = { cpu = 79.5, case = 72 }
+    "
+    )
+    .unwrap();
+
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                pub database: database::Database,
+                pub limit: limit::Limit
+            }
+
+            pub mod database {
+                pub struct Database {
+                    pub ports: ports::Ports,
+                    pub temp_targets: temp_targets::TempTargets
+                }
+
+                pub mod ports {
+                    pub type Ports = [values::Values; 2usize];
+
+                    pub mod values {
+                        pub type Values = u16;
+                    }
+                }
+
+                pub mod temp_targets {
+                    pub struct TempTargets {
+                        pub case: case::Case,
+                        pub cpu: cpu::Cpu
+                    }
+
+                    pub mod case {
+                        pub type Case = f32;
+                    }
+
+                    pub mod cpu {
+                        pub type Cpu = f32;
+                    }
+                }
+            }
+
+            pub mod limit {
+                pub type Limit = u32;
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn map_works() {
+    let config = StaticTomlAttributes {
+        maps: vec![MapTable {
+            path: parse_quote!(servers),
+            backend: MapBackend::Sorted
+        }],
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+    let servers = toml.get("servers").unwrap();
+
+    let servers_ts = servers
+        .type_tokens(
+            "servers",
+            &mut vec![crate::toml_tokens::PathSegment::Key("servers".to_string())],
+            &config,
+            quote!(pub),
+            &[]
+        )
+        .unwrap();
+    let servers_ts_expected = quote! {
+        pub mod servers {
+            pub struct Servers(pub &'static [(&'static str, values::Values)]);
+
+            impl Servers {
+                /// Returns the value for the key, if present.
+                ///
+                /// The entries are searched via binary search and therefore must
+                /// be sorted by key.
+                pub fn get(&self, key: &str) -> std::option::Option<&values::Values> {
+                    self.0
+                        .binary_search_by(|(k, _)| <str as std::cmp::Ord>::cmp(k, key))
+                        .ok()
+                        .map(|i| &self.0[i].1)
+                }
+
+                /// Iterates over the entries, sorted by key.
+                pub fn iter(&self) -> impl std::iter::Iterator<Item = (&str, &values::Values)> {
+                    self.0.iter().map(|(k, v)| (&**k, v))
+                }
+
+                /// Iterates over the keys in sorted order.
+                pub fn keys(&self) -> impl std::iter::Iterator<Item = &str> {
+                    self.0.iter().map(|(k, _)| &**k)
+                }
+
+                /// Returns the number of entries.
+                pub fn len(&self) -> usize {
+                    self.0.len()
+                }
+
+                /// Returns `true` if there are no entries.
+                pub fn is_empty(&self) -> bool {
+                    self.0.is_empty()
+                }
+            }
+
+            pub mod values {
+                pub struct Values {
+                    pub ip: ip::Ip,
+                    pub role: role::Role
+                }
+
+                pub mod ip {
+                    pub type Ip = &'static str;
+                }
+
+                pub mod role {
+                    pub type Role = &'static str;
+                }
+            }
+        }
+    };
+    assert_eq!(servers_ts.to_string(), servers_ts_expected.to_string());
+}
+
+#[test]
+#[cfg(feature = "phf")]
+fn phf_map_works() {
+    let config = StaticTomlAttributes {
+        maps: vec![MapTable {
+            path: parse_quote!(servers),
+            backend: MapBackend::Phf
+        }],
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(include_str!("../../../../example.toml")).unwrap();
+    let servers = toml.get("servers").unwrap();
+
+    let servers_ts = servers
+        .type_tokens(
+            "servers",
+            &mut vec![crate::toml_tokens::PathSegment::Key("servers".to_string())],
+            &config,
+            quote!(pub),
+            &[]
+        )
+        .unwrap()
+        .to_string();
+    let struct_expected = quote! {
+        pub struct Servers(pub ::static_toml::phf::Map<&'static str, values::Values>);
+    };
+    assert!(servers_ts.contains(&struct_expected.to_string()));
+    let get_expected = quote! {
+        pub fn get(&self, key: &str) -> std::option::Option<&values::Values> {
+            self.0.get(key)
+        }
+    };
+    assert!(servers_ts.contains(&get_expected.to_string()));
+}
+
+#[test]
+fn key_names_work() {
+    let config = StaticTomlAttributes {
+        renames: vec![parse_quote!("1st" = first)],
+        key_fallback: Some(KeyFallback::Transliterate),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    "1st" = 1
+    "2nd" = 2
+    "größe" = 3
+    type = "keyword"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                pub first: first::First,
+                pub _2_nd: _2_nd::_2Nd,
+                pub grosse: grosse::Grosse,
+                pub r#type: r#type::Type
+            }
+
+            pub mod first {
+                pub type First = i64;
+            }
+
+            pub mod _2_nd {
+                pub type _2Nd = i64;
+            }
+
+            pub mod grosse {
+                pub type Grosse = i64;
+            }
+
+            pub mod r#type {
+                pub type Type = &'static str;
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn key_fallback_prefix_works() {
+    let config = StaticTomlAttributes {
+        key_fallback: Some(KeyFallback::Prefix),
+        suffix: Some(format_ident!("Toml")),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    "1st" = 1
+    "max size" = 2
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct TomlToml {
+                pub _1_st: _1_st::_1StToml,
+                pub max_size: max_size::MaxSizeToml
+            }
+
+            pub mod _1_st {
+                pub type _1StToml = i64;
+            }
+
+            pub mod max_size {
+                pub type MaxSizeToml = i64;
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn serde_renames_work() {
+    let config = StaticTomlAttributes {
+        serde: Some(()),
+        mixed_arrays: Some(MixedArrays::Enum),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    fooBar = 1
+    type = "keyword"
+    plain = true
+    mixed = [1, "two"]
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                #[serde(rename = "fooBar")]
+                pub foo_bar: foo_bar::FooBar,
+                pub mixed: mixed::Mixed,
+                pub plain: plain::Plain,
+                pub r#type: r#type::Type
+            }
+
+            pub mod foo_bar {
+                pub type FooBar = i64;
+            }
+
+            pub mod mixed {
+                pub type Mixed = &'static [values::Values];
+
+                pub mod values {
+                    #[serde(untagged)]
+                    pub enum Values {
+                        Integer(integer::Integer),
+                        String(string::String)
+                    }
+
+                    pub mod integer {
+                        pub type Integer = i64;
+                    }
+
+                    pub mod string {
+                        pub type String = &'static str;
+                    }
+                }
+            }
+
+            pub mod plain {
+                pub type Plain = bool;
+            }
+
+            pub mod r#type {
+                pub type Type = &'static str;
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn runtime_layers_work() {
+    let toml: Value = toml::from_str(
+        r#"
+    pair = [1, "two"]
+
+    [database]
+    port = 8000
+    "#
+    )
+    .unwrap();
+
+    // Only owned data types can hold runtime values
+    let config = StaticTomlAttributes::default();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    assert!(!toml_ts.to_string().contains("Layer"));
+
+    let config = StaticTomlAttributes {
+        cow: Some(()),
+        ..StaticTomlAttributes::default()
+    };
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap()
+        .to_string();
+    for type_ident in ["Toml", "Database", "Pair"] {
+        let layer_impl = quote!(impl ::static_toml::runtime::Layer for).to_string();
+        assert!(toml_ts.contains(&format!("{layer_impl} {type_ident} {{")));
+    }
+    assert!(toml_ts.contains(&quote!("port" => ::static_toml::runtime::Layer::merge).to_string()));
+    assert!(toml_ts.contains(&quote!(&::static_toml::runtime::env_name(name, "port")).to_string()));
+}
+
+#[test]
+fn shared_types_work() {
+    let config = StaticTomlAttributes {
+        shared: vec![parse_quote!(servers.* = Server)],
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.alpha]
+    ip = "10.0.0.1"
+
+    [servers.beta]
+    ip = "10.0.0.2"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                pub servers: servers::Servers
+            }
+
+            pub mod servers {
+                pub struct Servers {
+                    pub alpha: alpha::Alpha,
+                    pub beta: beta::Beta
+                }
+
+                pub mod alpha {
+                    pub use super::super::server::*;
+                    pub type Alpha = Server;
+                }
+
+                pub mod beta {
+                    pub use super::super::server::*;
+                    pub type Beta = Server;
+                }
+            }
+
+            pub mod server {
+                pub struct Server {
+                    pub ip: ip::Ip
+                }
+
+                pub mod ip {
+                    pub type Ip = &'static str;
+                }
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+
+    let toml: Value = toml::from_str(
+        r#"
+    [servers.alpha]
+    ip = "10.0.0.1"
+
+    [servers.beta]
+    ip = 2
+    "#
+    )
+    .unwrap();
+    let error = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TomlError::SharedInvalid { path, name, .. }
+            if path_string(&path) == "servers.beta" && name == "Server"
+    ));
+}
+
+#[test]
+fn flat_layout_works() {
+    let config = StaticTomlAttributes {
+        layout: Some(Layout::Flat),
+        mixed_arrays: Some(MixedArrays::Enum),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    title = "flat"
+    created = 2024-01-01
+    ports = [8000, 8001]
+    mixed = [1, "a"]
+
+    [database]
+    enabled = true
+    grid = [[1.0, 2.0], [3.0, 4.0]]
+
+    [[servers]]
+    name = "alpha"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                pub created: ::static_toml::datetime::LocalDate,
+                pub database: database::Database,
+                pub mixed: mixed::Mixed,
+                pub ports: [i64; 2usize],
+                pub servers: servers::Servers,
+                pub title: &'static str
+            }
+
+            pub mod database {
+                pub struct Database {
+                    pub enabled: bool,
+                    pub grid: [[f64; 2usize]; 2usize]
+                }
+            }
+
+            pub mod mixed {
+                pub type Mixed = &'static [values::Values];
+
+                pub mod values {
+                    pub enum Values {
+                        Integer(i64),
+                        String(&'static str)
+                    }
+                }
+            }
+
+            pub mod servers {
+                pub type Servers = [values::Values; 1usize];
+
+                pub mod values {
+                    pub struct Values {
+                        pub name: &'static str
+                    }
+                }
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn path_attrs_work() {
+    let config = StaticTomlAttributes {
+        path_attrs: vec![
+            parse_quote!(owner = #[derive(Hash)]),
+            parse_quote!(owner.name = #[deprecated] #[doc = "Full name"]),
+            parse_quote!(pair.0 = #[doc = "First"]),
+        ],
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    pair = [1, "a"]
+
+    [owner]
+    name = "Tom"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                pub owner: owner::Owner,
+                pub pair: pair::Pair
+            }
+
+            pub mod owner {
+                #[derive(Hash)]
+                pub struct Owner {
+                    #[deprecated]
+                    #[doc = "Full name"]
+                    pub name: name::Name
+                }
+
+                pub mod name {
+                    pub type Name = &'static str;
+                }
+            }
+
+            pub mod pair {
+                pub struct Pair(#[doc = "First"] pub values_0::Values0, pub values_1::Values1);
+
+                pub mod values_0 {
+                    pub type Values0 = i64;
+                }
+
+                pub mod values_1 {
+                    pub type Values1 = &'static str;
+                }
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
+
+#[test]
+fn key_docs_work() {
+    let config = StaticTomlAttributes {
+        comments: [
+            ("database", "Database settings\nused by the server"),
+            ("database.port", "Port to listen on"),
+            ("servers.*.ip", "Address of the server")
+        ]
+        .into_iter()
+        .map(|(key, comment)| (key.to_string(), comment.to_string()))
+        .collect(),
+        ..StaticTomlAttributes::default()
+    };
+
+    let toml: Value = toml::from_str(
+        r#"
+    [database]
+    port = 8000
+
+    [[servers]]
+    ip = "10.0.0.1"
+    "#
+    )
+    .unwrap();
+    let toml_ts = toml
+        .type_tokens("toml", &mut Vec::new(), &config, quote!(pub), &[])
+        .unwrap();
+    let toml_ts_expected = quote! {
+        pub mod toml {
+            pub struct Toml {
+                #[doc = " Database settings"]
+                #[doc = " used by the server"]
+                pub database: database::Database,
+                pub servers: servers::Servers
+            }
+
+            #[doc = " Database settings"]
+            #[doc = " used by the server"]
+            pub mod database {
+                #[doc = " Database settings"]
+                #[doc = " used by the server"]
+                pub struct Database {
+                    #[doc = " Port to listen on"]
+                    pub port: port::Port
+                }
+
+                #[doc = " Port to listen on"]
+                pub mod port {
+                    #[doc = " Port to listen on"]
+                    pub type Port = i64;
+                }
+            }
+
+            pub mod servers {
+                pub type Servers = [values::Values; 1usize];
+
+                pub mod values {
+                    pub struct Values {
+                        #[doc = " Address of the server"]
+                        pub ip: ip::Ip
+                    }
+
+                    #[doc = " Address of the server"]
+                    pub mod ip {
+                        #[doc = " Address of the server"]
+                        pub type Ip = &'static str;
+                    }
+                }
+            }
+        }
+    };
+    assert_eq!(toml_ts.to_string(), toml_ts_expected.to_string());
+}
@@ -0,0 +1,530 @@
+//! Generates Rust tokens representing the types corresponding to TOML data.
+//!
+//! The `type_tokens` submodule focuses on generating Rust tokens that represent
+//! types based on TOML data structures. This facilitates the creation of Rust
+//! types that mirror the structure of the data in the TOML files.
+
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Attribute, Ident as Ident2};
+use toml::value::Array;
+use toml::{Table, Value};
+
+use crate::parse::{MapBackend, MixedArrays, StaticTomlAttributes};
+use crate::toml_tokens::runtime_tokens::{self, Field};
+use crate::toml_tokens::{
+    fixed_ident, CollectAll, PathSegment, TomlTokens, UnifiedKind, UnifiedTable
+};
+
+/// Generates the Rust tokens for the data type of a value contained in another
+/// data type.
+///
+/// Returns the type as referenced by the containing data type together with
+/// the module defining it.
+/// With `layout = flat`, leaf values are referenced by their Rust type and get
+/// no module.
+fn inner_type(
+    value: &Value,
+    key: &str,
+    reference: TokenStream2,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    derive: &[Attribute]
+) -> Result<(TokenStream2, TokenStream2), super::super::TomlError> {
+    match super::flat_type(value, path, config)? {
+        Some(flat_type) => Ok((flat_type, TokenStream2::new())),
+        None => Ok((
+            reference,
+            value.type_tokens(key, path, config, quote!(pub), derive)?
+        ))
+    }
+}
+
+/// Generates the Rust tokens for a TOML array type.
+///
+/// Returns a TokenStream2 representing the Rust code generated for the array
+/// type.
+#[inline]
+pub(crate) fn array(
+    array: &Array,
+    type_ident: &Ident2,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    derive: &[Attribute]
+) -> Result<TokenStream2, super::super::TomlError> {
+    // Check if slices should be used
+    let use_slices = super::use_slices(array, config);
+
+    // Define identifiers for the values
+    let values_ident = config
+        .values_ident
+        .as_ref()
+        .map(|i| i.to_string())
+        .unwrap_or_else(|| "values".to_string());
+    let values_mod_ident = format_ident!("{}", values_ident.to_case(Case::Snake));
+    let values_type_ident = format_ident!(
+        "{}",
+        fixed_ident(&values_ident, &config.prefix, &config.suffix)
+            .to_string()
+            .to_case(Case::Pascal)
+    );
+
+    // Generate tokens based on whether slices are used or not
+    if use_slices {
+        let len = array.len();
+        let Some(value) = array.first()
+        else {
+            return Ok(quote! {
+                pub type #type_ident = [(); 0];
+            });
+        };
+        // All items share the type of the first one
+        path.push(PathSegment::Item(0));
+        let unified = config
+            .unify_tables
+            .filter(|_| super::user_type(path, config).is_none())
+            .and_then(|_| super::unify_array(array));
+        let value_type_tokens = match unified {
+            Some(unified) => {
+                unified_table(&unified, &values_ident, path, config, quote!(pub), derive)
+                    .map(|tokens| (quote!(#values_mod_ident::#values_type_ident), tokens))
+            }
+            None => inner_type(
+                value,
+                &values_ident,
+                quote!(#values_mod_ident::#values_type_ident),
+                path,
+                config,
+                derive
+            )
+        };
+        path.pop();
+        let (value_type, value_type_tokens) = value_type_tokens?;
+
+        let docs = super::key_docs(path, config);
+        match config.cow {
+            None => Ok(quote! {
+                #(#docs)*
+                pub type #type_ident = [#value_type; #len];
+                #value_type_tokens
+            }),
+            Some(_) => Ok(quote! {
+                #(#docs)*
+                pub type #type_ident = std::borrow::Cow<'static, [#value_type]>;
+                #value_type_tokens
+            })
+        }
+    }
+    else if config.mixed_arrays == Some(MixedArrays::Enum) {
+        let (variants, indices) = super::enum_variants(array);
+        let tokens: Vec<(TokenStream2, TokenStream2)> = variants
+            .iter()
+            .enumerate()
+            .map(|(i, (k, v))| {
+                let item = indices
+                    .iter()
+                    .position(|&variant| variant == i)
+                    .unwrap_or(0);
+                let variant_ident = format_ident!("{}", k.to_case(Case::Pascal));
+                let mod_ident = format_ident!("{}", k.to_case(Case::Snake));
+                let type_ident = fixed_ident(k, &config.prefix, &config.suffix);
+                path.push(PathSegment::Variant(item));
+                let attrs = super::field_attrs(v, path, config);
+                let value = inner_type(v, k, quote!(#mod_ident::#type_ident), path, config, derive);
+                path.pop();
+                let (value_type, value_type_tokens) = value?;
+                Ok((
+                    quote!(#(#attrs)* #variant_ident(#value_type)),
+                    value_type_tokens
+                ))
+            })
+            .collect_all()?;
+        let (variant_tokens, variant_type_tokens): (Vec<TokenStream2>, Vec<TokenStream2>) =
+            tokens.into_iter().unzip();
+
+        let slice_type = match config.cow {
+            None => quote!(&'static [#values_mod_ident::#values_type_ident]),
+            Some(_) => quote!(std::borrow::Cow<'static, [#values_mod_ident::#values_type_ident]>)
+        };
+
+        // Variants are not tagged in TOML, serde has to match them by their data
+        let serde_untagged = config.serde.map(|_| quote!(#[serde(untagged)]));
+        let runtime_variants: Vec<(Ident2, &str)> = variants
+            .iter()
+            .map(|(k, v)| (format_ident!("{}", k.to_case(Case::Pascal)), v.type_str()))
+            .collect();
+        let runtime_tokens =
+            runtime_tokens::enumeration(&values_type_ident, &runtime_variants, config);
+        let type_attrs = super::path_attrs(path, config);
+        let docs = super::key_docs(path, config);
+
+        Ok(quote! {
+            #(#docs)*
+            pub type #type_ident = #slice_type;
+
+            pub mod #values_mod_ident {
+                #(#derive)*
+                #(#type_attrs)*
+                #serde_untagged
+                pub enum #values_type_ident {
+                    #(#variant_tokens),*
+                }
+
+                #runtime_tokens
+
+                #(#variant_type_tokens)*
+            }
+        })
+    }
+    else {
+        let tokens: Vec<(TokenStream2, TokenStream2)> = array
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let mod_ident = format_ident!("{}_{}", values_ident.to_case(Case::Snake), i);
+                let type_ident = format!("{}{}", values_ident.to_case(Case::Pascal), i);
+                let type_ident = fixed_ident(&type_ident, &config.prefix, &config.suffix);
+                path.push(PathSegment::Index(i));
+                let attrs = super::field_attrs(v, path, config);
+                let value = inner_type(
+                    v,
+                    &format!("{}{}", values_ident, i),
+                    quote!(#mod_ident::#type_ident),
+                    path,
+                    config,
+                    derive
+                );
+                path.pop();
+                let (value_type, value_type_tokens) = value?;
+                Ok((quote!(#(#attrs)* pub #value_type), value_type_tokens))
+            })
+            .collect_all()?;
+        let (value_types, value_tokens): (Vec<TokenStream2>, Vec<TokenStream2>) =
+            tokens.into_iter().unzip();
+
+        let runtime_tokens = runtime_tokens::tuple(type_ident, array.len(), config);
+        let type_attrs = super::path_attrs(path, config);
+        let docs = super::key_docs(path, config);
+
+        Ok(quote! {
+            #(#docs)*
+            #(#derive)*
+            #(#type_attrs)*
+            pub struct #type_ident(#(#value_types),*);
+            #runtime_tokens
+            #(#value_tokens)*
+        })
+    }
+}
+
+/// Generates the Rust tokens for a TOML table type.
+///
+/// Returns a TokenStream2 representing the Rust code generated for the table
+/// type.
+#[inline]
+pub(crate) fn table(
+    table: &Table,
+    type_ident: &Ident2,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    derive: &[Attribute]
+) -> Result<TokenStream2, super::super::TomlError> {
+    // Generate the field and inner modules tokens, the names of the keys are
+    // collected to detect collisions between them
+    let mut names: Vec<(&str, String)> = Vec::with_capacity(table.len());
+    let tokens: Vec<(TokenStream2, TokenStream2)> = table
+        .iter()
+        .map(|(k, v)| {
+            let name = super::key_name(k, path, config)?;
+            let previous = names.iter().map(|(k, name)| (*k, name.as_str()));
+            super::check_collision((k, &name), previous, path, config)?;
+
+            let field_key = super::snake_ident(&name);
+            let type_ident = super::fixed_ident(&name, &config.prefix, &config.suffix);
+            let serde_rename = super::serde_rename(k, &field_key, config);
+            path.push(PathSegment::Key(k.to_string()));
+            let attrs = super::field_attrs(v, path, config);
+            let docs = super::key_docs(path, config);
+            let value = inner_type(
+                v,
+                &name,
+                quote!(#field_key::#type_ident),
+                path,
+                config,
+                derive
+            );
+            path.pop();
+            names.push((k, name));
+            let (value_type, value_type_tokens) = value?;
+            Ok((
+                quote!(#(#docs)* #serde_rename #(#attrs)* pub #field_key: #value_type),
+                value_type_tokens
+            ))
+        })
+        .collect_all()?;
+    let (fields_tokens, mods_tokens): (Vec<TokenStream2>, Vec<TokenStream2>) =
+        tokens.into_iter().unzip();
+
+    let field_idents: Vec<Ident2> = names
+        .iter()
+        .map(|(_, name)| super::snake_ident(name))
+        .collect();
+    let fields: Vec<Field> = names
+        .iter()
+        .zip(field_idents.iter())
+        .map(|((key, _), ident)| Field {
+            key,
+            ident,
+            optional: false
+        })
+        .collect();
+    let runtime_tokens = runtime_tokens::table(type_ident, &fields, config);
+
+    // Shared data types are generated for multiple tables, their attributes
+    // are placed on the fields instead
+    let type_attrs = match super::shared_type(path, config) {
+        Some(_) => Vec::new(),
+        None => super::path_attrs(path, config)
+    };
+    let docs = super::key_docs(path, config);
+
+    // Combine the tokens into the final structure
+    Ok(quote! {
+        #(#docs)*
+        #(#derive)*
+        #(#type_attrs)*
+        pub struct #type_ident {
+            #(#fields_tokens),*
+        }
+
+        #runtime_tokens
+
+        #(#mods_tokens)*
+    })
+}
+
+/// Generates the Rust tokens for a TOML table configured via `map`.
+///
+/// Instead of a struct with a field per key, a tuple struct wrapping the
+/// entries is generated, together with methods to look up and iterate them.
+/// The entries are either a slice sorted by key or, with `map(phf)`, a
+/// `phf::Map`.
+#[inline]
+pub(crate) fn map(
+    table: &Table,
+    type_ident: &Ident2,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    derive: &[Attribute]
+) -> Result<TokenStream2, super::super::TomlError> {
+    let unified = super::map_values(table, path, config)?;
+
+    // Define identifiers for the values
+    let values_ident = config
+        .values_ident
+        .as_ref()
+        .map(|i| i.to_string())
+        .unwrap_or_else(|| "values".to_string());
+    let values_mod_ident = format_ident!("{}", values_ident.to_case(Case::Snake));
+    let values_type_ident = fixed_ident(&values_ident, &config.prefix, &config.suffix);
+    let values_type = quote!(#values_mod_ident::#values_type_ident);
+
+    // All entries share the type of the first one
+    let (value_type, value_type_tokens) = match (table.iter().next(), unified) {
+        (None, _) => Ok((values_type.clone(), quote! {
+            pub mod #values_mod_ident {
+                pub type #values_type_ident = ();
+            }
+        })),
+        (Some((k, _)), Some(unified)) => {
+            path.push(PathSegment::Entry(k.to_string()));
+            let value = unified_table(&unified, &values_ident, path, config, quote!(pub), derive);
+            path.pop();
+            value.map(|tokens| (values_type, tokens))
+        }
+        (Some((k, v)), None) => {
+            path.push(PathSegment::Entry(k.to_string()));
+            let value = inner_type(v, &values_ident, values_type, path, config, derive);
+            path.pop();
+            value
+        }
+    }?;
+
+    let backend = super::map_backend(path, config).unwrap_or(MapBackend::Sorted);
+    let (entries_type, methods) = match backend {
+        #[cfg(feature = "phf")]
+        MapBackend::Phf => (
+            quote!(::static_toml::phf::Map<&'static str, #value_type>),
+            quote! {
+                /// Returns the value for the key, if present.
+                pub fn get(&self, key: &str) -> std::option::Option<&#value_type> {
+                    self.0.get(key)
+                }
+
+                /// Iterates over the entries in an unspecified order.
+                pub fn iter(&self) -> impl std::iter::Iterator<Item = (&str, &#value_type)> {
+                    self.0.entries().map(|(k, v)| (*k, v))
+                }
+
+                /// Iterates over the keys in an unspecified order.
+                pub fn keys(&self) -> impl std::iter::Iterator<Item = &str> {
+                    self.0.keys().copied()
+                }
+            }
+        ),
+        MapBackend::Sorted => (
+            match config.cow {
+                None => quote!(&'static [(&'static str, #value_type)]),
+                Some(_) => quote! {
+                    std::borrow::Cow<'static, [(std::borrow::Cow<'static, str>, #value_type)]>
+                }
+            },
+            quote! {
+                /// Returns the value for the key, if present.
+                ///
+                /// The entries are searched via binary search and therefore must
+                /// be sorted by key.
+                pub fn get(&self, key: &str) -> std::option::Option<&#value_type> {
+                    self.0
+                        .binary_search_by(|(k, _)| <str as std::cmp::Ord>::cmp(k, key))
+                        .ok()
+                        .map(|i| &self.0[i].1)
+                }
+
+                /// Iterates over the entries, sorted by key.
+                pub fn iter(&self) -> impl std::iter::Iterator<Item = (&str, &#value_type)> {
+                    self.0.iter().map(|(k, v)| (&**k, v))
+                }
+
+                /// Iterates over the keys in sorted order.
+                pub fn keys(&self) -> impl std::iter::Iterator<Item = &str> {
+                    self.0.iter().map(|(k, _)| &**k)
+                }
+            }
+        )
+    };
+
+    let runtime_tokens = runtime_tokens::map(type_ident, backend, config);
+    let type_attrs = super::path_attrs(path, config);
+    let docs = super::key_docs(path, config);
+
+    Ok(quote! {
+        #(#docs)*
+        #(#derive)*
+        #(#type_attrs)*
+        pub struct #type_ident(pub #entries_type);
+
+        #runtime_tokens
+
+        impl #type_ident {
+            #methods
+
+            /// Returns the number of entries.
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Returns `true` if there are no entries.
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+        }
+
+        #value_type_tokens
+    })
+}
+
+/// Generates the Rust tokens for tables unified via `unify_tables`.
+///
+/// Works like [`TomlTokens::type_tokens`] for a table but generates a single
+/// type for multiple tables.
+/// Fields that are missing in some of the tables are wrapped in an [`Option`].
+pub(crate) fn unified_table(
+    unified: &UnifiedTable,
+    key: &str,
+    path: &mut Vec<PathSegment>,
+    config: &StaticTomlAttributes,
+    visibility: TokenStream2,
+    derive: &[Attribute]
+) -> Result<TokenStream2, super::super::TomlError> {
+    let mod_ident = super::snake_ident(key);
+    let type_ident = fixed_ident(key, &config.prefix, &config.suffix);
+
+    // Generate the field and inner modules tokens, optional fields are wrapped
+    // in an `Option`
+    let mut names: Vec<(&str, String)> = Vec::with_capacity(unified.fields.len());
+    let tokens: Vec<(TokenStream2, TokenStream2)> = unified
+        .fields
+        .iter()
+        .map(|(k, field)| {
+            let name = super::key_name(k, path, config)?;
+            let previous = names.iter().map(|(k, name)| (*k, name.as_str()));
+            super::check_collision((k, &name), previous, path, config)?;
+
+            let field_key = super::snake_ident(&name);
+            let type_ident = fixed_ident(&name, &config.prefix, &config.suffix);
+            let reference = quote!(#field_key::#type_ident);
+            path.push(PathSegment::Key(k.to_string()));
+            let attrs = match &field.kind {
+                UnifiedKind::Value(v) => super::field_attrs(v, path, config),
+                UnifiedKind::Table(_) => Vec::new()
+            };
+            let docs = super::key_docs(path, config);
+            let value = match &field.kind {
+                UnifiedKind::Value(v) => inner_type(v, &name, reference, path, config, derive),
+                UnifiedKind::Table(t) => unified_table(t, &name, path, config, quote!(pub), derive)
+                    .map(|tokens| (reference, tokens))
+            };
+            path.pop();
+            names.push((k, name));
+            let (value_type, value_type_tokens) = value?;
+
+            let serde_rename = super::serde_rename(k, &field_key, config);
+            let field_tokens = match field.optional {
+                false => quote!(#(#docs)* #serde_rename #(#attrs)* pub #field_key: #value_type),
+                true => quote! {
+                    #(#docs)*
+                    #serde_rename #(#attrs)* pub #field_key: std::option::Option<#value_type>
+                }
+            };
+            Ok((field_tokens, value_type_tokens))
+        })
+        .collect_all()?;
+    let (fields_tokens, mods_tokens): (Vec<TokenStream2>, Vec<TokenStream2>) =
+        tokens.into_iter().unzip();
+
+    let field_idents: Vec<Ident2> = names
+        .iter()
+        .map(|(_, name)| super::snake_ident(name))
+        .collect();
+    let fields: Vec<Field> = unified
+        .fields
+        .iter()
+        .zip(field_idents.iter())
+        .map(|((key, field), ident)| Field {
+            key,
+            ident,
+            optional: field.optional
+        })
+        .collect();
+    let runtime_tokens = runtime_tokens::table(&type_ident, &fields, config);
+    let type_attrs = super::path_attrs(path, config);
+    let docs = super::key_docs(path, config);
+
+    Ok(quote! {
+        #(#docs)*
+        #visibility mod #mod_ident {
+            #(#docs)*
+            #(#derive)*
+            #(#type_attrs)*
+            pub struct #type_ident {
+                #(#fields_tokens),*
+            }
+
+            #runtime_tokens
+
+            #(#mods_tokens)*
+        }
+    })
+}
@@ -0,0 +1,126 @@
+//! Looks up the fields of existing types configured via `use_type`.
+//!
+//! A procedural macro cannot resolve types, so the `user_types` module
+//! searches the structs defined in the source file of the macro call by their
+//! name instead.
+//! This covers types written next to the static value, keys of other types are
+//! left to rustc.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use proc_macro2::{Delimiter, TokenStream as TokenStream2, TokenTree};
+use syn::ext::IdentExt;
+use syn::FieldsNamed;
+
+/// Collects the named fields of the structs in the source by the name of the
+/// struct.
+///
+/// Names of multiple structs, e.g. in different modules, are left out, as the
+/// macro cannot tell which of them a type refers to.
+pub(crate) fn collect(source: &str) -> HashMap<String, Vec<String>> {
+    let mut structs = HashMap::new();
+    if let Ok(tokens) = TokenStream2::from_str(source) {
+        collect_tokens(tokens, &mut structs);
+    }
+
+    structs
+        .into_iter()
+        .filter_map(|(name, fields)| Some((name, fields?)))
+        .collect()
+}
+
+/// Collects the structs of the tokens and of every group within them.
+///
+/// Structs without named fields, or with a body that cannot be told apart,
+/// are collected as `None`.
+fn collect_tokens(tokens: TokenStream2, structs: &mut HashMap<String, Option<Vec<String>>>) {
+    let mut tokens = tokens.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "struct" => {
+                let Some(TokenTree::Ident(name)) = tokens.next()
+                else {
+                    continue;
+                };
+
+                // Generics and where clauses are skipped up to the body.
+                let body = tokens.find_map(|token| match token {
+                    TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                        Some(Some(group))
+                    }
+                    TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                        Some(None)
+                    }
+                    TokenTree::Punct(punct) if punct.as_char() == ';' => Some(None),
+                    _ => None
+                });
+                let fields = body
+                    .flatten()
+                    .and_then(|body| syn::parse2::<FieldsNamed>(TokenTree::Group(body).into()).ok())
+                    .map(|fields| {
+                        fields
+                            .named
+                            .iter()
+                            .filter_map(|field| field.ident.as_ref())
+                            .map(|ident| ident.unraw().to_string())
+                            .collect()
+                    });
+
+                structs
+                    .entry(name.unraw().to_string())
+                    .and_modify(|known| *known = None)
+                    .or_insert(fields);
+            }
+            TokenTree::Group(group) => collect_tokens(group.stream(), structs),
+            _ => ()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_works() {
+        let source = r#"
+        pub struct ServerConfig {
+            pub ip: &'static str,
+            pub r#type: &'static str
+        }
+
+        struct Generic<T: Default> where T: Clone {
+            value: T
+        }
+
+        struct Tuple(u8);
+
+        mod a {
+            pub struct Owner {
+                name: &'static str
+            }
+        }
+
+        mod b {
+            pub struct Owner {
+                email: &'static str
+            }
+        }
+
+        fn main() {
+            struct Local {
+                enabled: bool
+            }
+        }
+        "#;
+
+        let structs = collect(source);
+        assert_eq!(structs["ServerConfig"], ["ip", "type"]);
+        assert_eq!(structs["Generic"], ["value"]);
+        assert_eq!(structs["Local"], ["enabled"]);
+        assert!(!structs.contains_key("Tuple"));
+        assert!(!structs.contains_key("Owner"));
+        assert_eq!(structs.len(), 3);
+    }
+}
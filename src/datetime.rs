@@ -0,0 +1,578 @@
+//! Date and time types for TOML datetimes.
+//!
+//! TOML knows four kinds of datetimes: offset datetimes, local datetimes,
+//! local dates and local times.
+//! The [`static_toml!`](crate::static_toml) macro maps each of them onto its
+//! own type in this module, so the distinction survives the embedding.
+//! All types can be constructed in `const` contexts, which allows the macro to
+//! emit them into `static` and `const` items.
+//!
+//! With the `chrono` or `time` features enabled, the types can be converted
+//! into the corresponding types of these crates.
+//! The `serde` feature implements `Serialize` and `Deserialize` using the
+//! RFC 3339 representation that TOML uses.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A local date, e.g. `1979-05-27`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocalDate {
+    year: u16,
+    month: u8,
+    day: u8
+}
+
+/// A local time, e.g. `07:32:00.999999`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct LocalTime {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32
+}
+
+/// A time offset from UTC, e.g. `Z` or `-08:00`.
+///
+/// Defaults to [`Offset::Z`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Offset {
+    /// Minutes in the range of `-1439..=1439`, `None` for `Z`.
+    minutes: Option<i16>
+}
+
+/// A local datetime, e.g. `1979-05-27T07:32:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct LocalDatetime {
+    date: LocalDate,
+    time: LocalTime
+}
+
+/// An offset datetime, e.g. `1979-05-27T07:32:00-08:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct OffsetDatetime {
+    date: LocalDate,
+    time: LocalTime,
+    offset: Offset
+}
+
+/// Error returned when parsing a datetime from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatetimeParseError {
+    expected: &'static str
+}
+
+impl LocalDate {
+    /// Creates a new date.
+    ///
+    /// # Panics
+    /// Panics if the month or day are out of range.
+    /// In `const` contexts this results in a compile error.
+    pub const fn new(year: u16, month: u8, day: u8) -> Self {
+        assert!(month >= 1 && month <= 12, "month out of range");
+        assert!(
+            day >= 1 && day <= days_in_month(year, month),
+            "day out of range"
+        );
+        LocalDate { year, month, day }
+    }
+
+    /// The year, e.g. `1979`.
+    pub const fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// The month, from `1` to `12`.
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month, from `1` to `31`.
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl Default for LocalDate {
+    /// Returns the unix epoch, `1970-01-01`.
+    fn default() -> Self {
+        LocalDate::new(1970, 1, 1)
+    }
+}
+
+impl LocalTime {
+    /// Creates a new time.
+    ///
+    /// A `second` of `60` is accepted to represent leap seconds.
+    ///
+    /// # Panics
+    /// Panics if any component is out of range.
+    /// In `const` contexts this results in a compile error.
+    pub const fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Self {
+        assert!(hour < 24, "hour out of range");
+        assert!(minute < 60, "minute out of range");
+        assert!(second <= 60, "second out of range");
+        assert!(nanosecond < 1_000_000_000, "nanosecond out of range");
+        LocalTime {
+            hour,
+            minute,
+            second,
+            nanosecond
+        }
+    }
+
+    /// The hour, from `0` to `23`.
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// The minute, from `0` to `59`.
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// The second, from `0` to `60`, where `60` denotes a leap second.
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// The fraction of the second in nanoseconds.
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+}
+
+impl Offset {
+    /// UTC, written as `Z`.
+    pub const Z: Offset = Offset { minutes: None };
+
+    /// Creates an offset of `minutes` from UTC.
+    ///
+    /// # Panics
+    /// Panics if the offset is not within a day.
+    /// In `const` contexts this results in a compile error.
+    pub const fn custom(minutes: i16) -> Self {
+        assert!(minutes > -1440 && minutes < 1440, "offset out of range");
+        Offset {
+            minutes: Some(minutes)
+        }
+    }
+
+    /// The offset from UTC in minutes, `0` for `Z`.
+    pub const fn minutes(&self) -> i16 {
+        match self.minutes {
+            Some(minutes) => minutes,
+            None => 0
+        }
+    }
+}
+
+impl LocalDatetime {
+    /// Creates a new local datetime.
+    pub const fn new(date: LocalDate, time: LocalTime) -> Self {
+        LocalDatetime { date, time }
+    }
+
+    /// The date part.
+    pub const fn date(&self) -> LocalDate {
+        self.date
+    }
+
+    /// The time part.
+    pub const fn time(&self) -> LocalTime {
+        self.time
+    }
+}
+
+impl OffsetDatetime {
+    /// Creates a new offset datetime.
+    pub const fn new(date: LocalDate, time: LocalTime, offset: Offset) -> Self {
+        OffsetDatetime { date, time, offset }
+    }
+
+    /// The date part.
+    pub const fn date(&self) -> LocalDate {
+        self.date
+    }
+
+    /// The time part.
+    pub const fn time(&self) -> LocalTime {
+        self.time
+    }
+
+    /// The offset from UTC.
+    pub const fn offset(&self) -> Offset {
+        self.offset
+    }
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    let leap_year =
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month {
+        2 if leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+impl Display for LocalDate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Display for LocalTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond != 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Offset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.minutes {
+            None => write!(f, "Z"),
+            Some(minutes) => {
+                let sign = if minutes < 0 { '-' } else { '+' };
+                let minutes = minutes.unsigned_abs();
+                write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+impl Display for LocalDatetime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)
+    }
+}
+
+impl Display for OffsetDatetime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}T{}{}", self.date, self.time, self.offset)
+    }
+}
+
+impl Display for DatetimeParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to parse {}", self.expected)
+    }
+}
+
+impl Error for DatetimeParseError {}
+
+/// Components of a parsed TOML datetime, whichever are present.
+type DatetimeParts = (Option<LocalDate>, Option<LocalTime>, Option<Offset>);
+
+/// Parses a TOML datetime and splits it into its components.
+fn parse_toml_datetime(
+    s: &str,
+    expected: &'static str
+) -> Result<DatetimeParts, DatetimeParseError> {
+    let datetime = toml_datetime::Datetime::from_str(s).or(Err(DatetimeParseError { expected }))?;
+    let date = datetime
+        .date
+        .map(|d| LocalDate::new(d.year, d.month, d.day));
+    let time = datetime
+        .time
+        .map(|t| LocalTime::new(t.hour, t.minute, t.second, t.nanosecond));
+    let offset = datetime.offset.map(|o| match o {
+        toml_datetime::Offset::Z => Offset::Z,
+        toml_datetime::Offset::Custom { minutes } => Offset::custom(minutes)
+    });
+    Ok((date, time, offset))
+}
+
+impl FromStr for LocalDate {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "local date";
+        match parse_toml_datetime(s, EXPECTED)? {
+            (Some(date), None, None) => Ok(date),
+            _ => Err(DatetimeParseError { expected: EXPECTED })
+        }
+    }
+}
+
+impl FromStr for LocalTime {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "local time";
+        match parse_toml_datetime(s, EXPECTED)? {
+            (None, Some(time), None) => Ok(time),
+            _ => Err(DatetimeParseError { expected: EXPECTED })
+        }
+    }
+}
+
+impl FromStr for LocalDatetime {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "local datetime";
+        match parse_toml_datetime(s, EXPECTED)? {
+            (Some(date), Some(time), None) => Ok(LocalDatetime { date, time }),
+            _ => Err(DatetimeParseError { expected: EXPECTED })
+        }
+    }
+}
+
+impl FromStr for OffsetDatetime {
+    type Err = DatetimeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "offset datetime";
+        match parse_toml_datetime(s, EXPECTED)? {
+            (Some(date), Some(time), Some(offset)) => Ok(OffsetDatetime { date, time, offset }),
+            _ => Err(DatetimeParseError { expected: EXPECTED })
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+
+    impl From<LocalDate> for chrono::NaiveDate {
+        fn from(date: LocalDate) -> Self {
+            chrono::NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+                .expect("validated on construction")
+        }
+    }
+
+    impl From<LocalTime> for chrono::NaiveTime {
+        /// Converts the time, leap seconds are represented the way `chrono`
+        /// expects them, as a nanosecond overflow of the 59th second.
+        fn from(time: LocalTime) -> Self {
+            let (second, nanosecond) = match time.second {
+                60 => (59, time.nanosecond + 1_000_000_000),
+                second => (second, time.nanosecond)
+            };
+            chrono::NaiveTime::from_hms_nano_opt(
+                time.hour.into(),
+                time.minute.into(),
+                second.into(),
+                nanosecond
+            )
+            .expect("validated on construction")
+        }
+    }
+
+    impl From<Offset> for chrono::FixedOffset {
+        fn from(offset: Offset) -> Self {
+            chrono::FixedOffset::east_opt(i32::from(offset.minutes()) * 60)
+                .expect("validated on construction")
+        }
+    }
+
+    impl From<LocalDatetime> for chrono::NaiveDateTime {
+        fn from(datetime: LocalDatetime) -> Self {
+            chrono::NaiveDateTime::new(datetime.date.into(), datetime.time.into())
+        }
+    }
+
+    impl From<OffsetDatetime> for chrono::DateTime<chrono::FixedOffset> {
+        fn from(datetime: OffsetDatetime) -> Self {
+            chrono::NaiveDateTime::new(datetime.date.into(), datetime.time.into())
+                .and_local_timezone(chrono::FixedOffset::from(datetime.offset))
+                .single()
+                .expect("fixed offsets are never ambiguous")
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::error::ComponentRange;
+
+    use super::*;
+
+    impl TryFrom<LocalDate> for time::Date {
+        type Error = ComponentRange;
+
+        fn try_from(date: LocalDate) -> Result<Self, Self::Error> {
+            time::Date::from_calendar_date(
+                date.year.into(),
+                time::Month::try_from(date.month)?,
+                date.day
+            )
+        }
+    }
+
+    /// Fails for leap seconds, as `time` does not support them.
+    impl TryFrom<LocalTime> for time::Time {
+        type Error = ComponentRange;
+
+        fn try_from(time: LocalTime) -> Result<Self, Self::Error> {
+            time::Time::from_hms_nano(time.hour, time.minute, time.second, time.nanosecond)
+        }
+    }
+
+    impl TryFrom<Offset> for time::UtcOffset {
+        type Error = ComponentRange;
+
+        fn try_from(offset: Offset) -> Result<Self, Self::Error> {
+            time::UtcOffset::from_whole_seconds(i32::from(offset.minutes()) * 60)
+        }
+    }
+
+    impl TryFrom<LocalDatetime> for time::PrimitiveDateTime {
+        type Error = ComponentRange;
+
+        fn try_from(datetime: LocalDatetime) -> Result<Self, Self::Error> {
+            Ok(time::PrimitiveDateTime::new(
+                datetime.date.try_into()?,
+                datetime.time.try_into()?
+            ))
+        }
+    }
+
+    impl TryFrom<OffsetDatetime> for time::OffsetDateTime {
+        type Error = ComponentRange;
+
+        fn try_from(datetime: OffsetDatetime) -> Result<Self, Self::Error> {
+            let primitive =
+                time::PrimitiveDateTime::new(datetime.date.try_into()?, datetime.time.try_into()?);
+            Ok(primitive.assume_offset(datetime.offset.try_into()?))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt::Formatter;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    /// Field name the `toml` crate uses to pass datetimes through serde.
+    const TOML_DATETIME_FIELD: &str = "$__toml_private_datetime";
+
+    /// Visitor accepting plain strings and datetimes from the `toml` crate.
+    struct DatetimeVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: FromStr<Err = DatetimeParseError>> Visitor<'de> for DatetimeVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+            formatter.write_str("a TOML datetime")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.parse().map_err(E::custom)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            match map.next_key::<String>()? {
+                Some(key) if key == TOML_DATETIME_FIELD => map
+                    .next_value::<String>()?
+                    .parse()
+                    .map_err(de::Error::custom),
+                _ => Err(de::Error::custom("expected a TOML datetime"))
+            }
+        }
+    }
+
+    macro_rules! impl_serde {
+        ($($ty:ty),*) => {$(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_any(DatetimeVisitor(PhantomData))
+                }
+            }
+        )*};
+    }
+
+    impl_serde!(LocalDate, LocalTime, LocalDatetime, OffsetDatetime);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_matches_toml() {
+        for input in [
+            "1979-05-27T07:32:00-08:00",
+            "1979-05-27T00:32:00.999999Z",
+            "1979-05-27T07:32:00+05:30"
+        ] {
+            let datetime: OffsetDatetime = input.parse().unwrap();
+            assert_eq!(datetime.to_string(), input);
+        }
+
+        let datetime: LocalDatetime = "1979-05-27T07:32:00".parse().unwrap();
+        assert_eq!(datetime.to_string(), "1979-05-27T07:32:00");
+        let date: LocalDate = "1979-05-27".parse().unwrap();
+        assert_eq!(date.to_string(), "1979-05-27");
+        let time: LocalTime = "00:32:00.5".parse().unwrap();
+        assert_eq!(time.to_string(), "00:32:00.5");
+    }
+
+    #[test]
+    fn kinds_are_distinguished() {
+        assert!("1979-05-27".parse::<LocalDatetime>().is_err());
+        assert!("1979-05-27T07:32:00".parse::<OffsetDatetime>().is_err());
+        assert!("07:32:00".parse::<LocalDate>().is_err());
+    }
+
+    #[test]
+    fn const_construction_works() {
+        const DOB: OffsetDatetime = OffsetDatetime::new(
+            LocalDate::new(1979, 5, 27),
+            LocalTime::new(7, 32, 0, 0),
+            Offset::custom(-480)
+        );
+        assert_eq!(DOB.date().year(), 1979);
+        assert_eq!(DOB.offset().minutes(), -480);
+    }
+
+    #[test]
+    fn offsets_work() {
+        assert_eq!(Offset::default(), Offset::Z);
+        assert_eq!(Offset::Z.minutes(), 0);
+        assert_eq!(Offset::Z.to_string(), "Z");
+        assert_ne!(Offset::custom(0), Offset::Z);
+        assert_eq!(Offset::custom(0).to_string(), "+00:00");
+        assert_eq!(Offset::custom(330).to_string(), "+05:30");
+
+        let datetime: OffsetDatetime = "1979-05-27T07:32:00Z".parse().unwrap();
+        assert_eq!(datetime.offset(), Offset::Z);
+    }
+
+    #[test]
+    #[should_panic(expected = "offset out of range")]
+    fn offsets_are_range_checked() {
+        Offset::custom(10_000);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_from_toml_works() {
+        #[derive(serde::Deserialize)]
+        struct Owner {
+            dob: OffsetDatetime
+        }
+
+        let owner: Owner = toml::from_str("dob = 1979-05-27T07:32:00-08:00").unwrap();
+        assert_eq!(owner.dob.to_string(), "1979-05-27T07:32:00-08:00");
+    }
+}
@@ -0,0 +1,634 @@
+//! Runtime overrides of embedded values.
+//!
+//! With the `runtime` feature enabled, the
+//! [`static_toml!`](crate::static_toml) macro generates a
+//! `load_with_overrides` function for the root type of every static value
+//! using `cow`.
+//! It reads a TOML file at runtime, validates it against the embedded data and
+//! returns an owned value where every key missing in the file keeps its
+//! embedded value.
+//!
+//! The generated data types implement [`Layer`] to read and merge runtime
+//! values, this module provides the implementations for the primitive types
+//! and the helpers used by the generated code.
+//! Tables are merged key by key, all other values, including arrays, are
+//! replaced as a whole.
+//!
+//! The root type also gets a `from_env` function, overriding the embedded
+//! leaf values from environment variables named after their path, e.g.
+//! `APP_DATABASE_PORTS_0` for the first port with the prefix `APP`.
+//! See [`env_name`] for how the names are built.
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, error, fs, io};
+
+pub use toml;
+use toml::{Table, Value};
+
+use crate::datetime::{LocalDate, LocalDatetime, LocalTime, OffsetDatetime};
+
+/// Error returned when loading runtime overrides fails.
+///
+/// Paths to values are written like `database.ports.1`.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the file failed.
+    Read(PathBuf, io::Error),
+    /// The file is no valid TOML.
+    Parse(PathBuf, toml::de::Error),
+    /// A key is not part of the embedded data.
+    UnknownKey(String),
+    /// A key required for a new value is missing.
+    MissingKey(String),
+    /// A value has a different type than the embedded one.
+    TypeMismatch {
+        path: String,
+        expected: &'static str,
+        found: &'static str
+    },
+    /// An integer does not fit into the embedded numeric type.
+    ValueOutOfRange {
+        path: String,
+        value: i64,
+        ty: &'static str
+    },
+    /// An array has a different length than the embedded tuple.
+    LengthMismatch {
+        path: String,
+        expected: usize,
+        found: usize
+    },
+    /// An environment variable cannot be parsed into the embedded type.
+    Env {
+        name: String,
+        value: String,
+        expected: &'static str
+    },
+    /// The value cannot be overridden, like maps using `phf`.
+    Unsupported(String)
+}
+
+/// Entries of a map owning runtime values, sorted by key.
+pub type MapEntries<V> = Cow<'static, [(Cow<'static, str>, V)]>;
+
+/// Data types that can be read from and merged with runtime TOML values.
+pub trait Layer: Sized {
+    /// Reads a complete value, every key has to be present.
+    fn from_toml(value: Value, path: &str) -> Result<Self, Error>;
+
+    /// Merges the value into `self`.
+    ///
+    /// By default the value is replaced, tables override this to keep the
+    /// values of missing keys.
+    fn merge(&mut self, value: Value, path: &str) -> Result<(), Error> {
+        *self = Self::from_toml(value, path)?;
+        Ok(())
+    }
+
+    /// Overrides the leaf values from the environment variables named after
+    /// `name`.
+    fn merge_env(&mut self, name: &str) -> Result<(), Error>;
+}
+
+/// Merges the TOML file at `path` into the embedded value.
+pub fn load<T: Layer>(mut embedded: T, path: &Path) -> Result<T, Error> {
+    let content = fs::read_to_string(path).map_err(|e| Error::Read(path.to_path_buf(), e))?;
+    let table: Table = toml::from_str(&content).map_err(|e| Error::Parse(path.to_path_buf(), e))?;
+    embedded.merge(Value::Table(table), "")?;
+    Ok(embedded)
+}
+
+/// Overrides the leaf values of the embedded value from environment
+/// variables starting with `prefix`.
+pub fn from_env<T: Layer>(mut embedded: T, prefix: &str) -> Result<T, Error> {
+    embedded.merge_env(prefix)?;
+    Ok(embedded)
+}
+
+/// Appends a key or index to the name of an environment variable.
+///
+/// The key is uppercased and every character that is not alphanumeric is
+/// replaced by `_`, so `temp-targets` below `APP_DATABASE` becomes
+/// `APP_DATABASE_TEMP_TARGETS`.
+pub fn env_name(name: &str, key: impl Display) -> String {
+    let key: String = key
+        .to_string()
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_uppercase(),
+            false => '_'
+        })
+        .collect();
+    match name.is_empty() {
+        true => key,
+        false => format!("{name}_{key}")
+    }
+}
+
+/// Reads the environment variable, if set.
+pub fn env_var(name: &str) -> Result<Option<String>, Error> {
+    match env::var(name) {
+        Ok(value) => Ok(Some(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(env::VarError::NotUnicode(value)) => Err(Error::Env {
+            name: name.to_string(),
+            value: value.to_string_lossy().into_owned(),
+            expected: "unicode"
+        })
+    }
+}
+
+/// Reads and parses the environment variable, if set.
+pub fn parse_env<T: FromStr>(name: &str, expected: &'static str) -> Result<Option<T>, Error> {
+    env_var(name)?
+        .map(|value| {
+            value.parse().map_err(|_| Error::Env {
+                name: name.to_string(),
+                value,
+                expected
+            })
+        })
+        .transpose()
+}
+
+/// Overrides the values of the existing entries of a map from environment
+/// variables.
+pub fn merge_map_env<V: Layer + Clone>(
+    entries: &mut MapEntries<V>,
+    name: &str
+) -> Result<(), Error> {
+    for (key, value) in entries.to_mut() {
+        value.merge_env(&env_name(name, key))?;
+    }
+    Ok(())
+}
+
+/// Appends a key or index to a path.
+pub fn child_path(path: &str, key: impl Display) -> String {
+    match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{path}.{key}")
+    }
+}
+
+/// Unwraps a table, anything else is a type mismatch.
+pub fn table(value: Value, path: &str) -> Result<Table, Error> {
+    match value {
+        Value::Table(table) => Ok(table),
+        value => Err(type_mismatch(&value, path, "table"))
+    }
+}
+
+/// Removes the key from the table and reads its value.
+pub fn field<T: Layer>(table: &mut Table, key: &str, path: &str) -> Result<T, Error> {
+    let path = child_path(path, key);
+    match table.remove(key) {
+        Some(value) => T::from_toml(value, &path),
+        None => Err(Error::MissingKey(path))
+    }
+}
+
+/// Removes the key from the table and reads its value, if present.
+pub fn optional_field<T: Layer>(
+    table: &mut Table,
+    key: &str,
+    path: &str
+) -> Result<Option<T>, Error> {
+    match table.remove(key) {
+        Some(value) => T::from_toml(value, &child_path(path, key)).map(Some),
+        None => Ok(None)
+    }
+}
+
+/// Fails for the first key left in the table.
+pub fn deny_unknown(table: Table, path: &str) -> Result<(), Error> {
+    match table.into_iter().next() {
+        Some((key, _)) => Err(Error::UnknownKey(child_path(path, key))),
+        None => Ok(())
+    }
+}
+
+/// Unwraps an array of exactly `N` values, used for tuples.
+pub fn tuple<const N: usize>(value: Value, path: &str) -> Result<[Value; N], Error> {
+    let array = match value {
+        Value::Array(array) => array,
+        value => return Err(type_mismatch(&value, path, "array"))
+    };
+    let found = array.len();
+    array.try_into().map_err(|_| Error::LengthMismatch {
+        path: path.to_string(),
+        expected: N,
+        found
+    })
+}
+
+/// Reads the entries of a map, sorted by key.
+pub fn map<V: Layer + Clone>(value: Value, path: &str) -> Result<MapEntries<V>, Error> {
+    let mut entries = table(value, path)?
+        .into_iter()
+        .map(|(key, value)| {
+            let value = V::from_toml(value, &child_path(path, &key))?;
+            Ok((Cow::Owned(key), value))
+        })
+        .collect::<Result<Vec<(Cow<'static, str>, V)>, Error>>()?;
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(Cow::Owned(entries))
+}
+
+/// Merges the entries of a table into the entries of a map.
+///
+/// Existing entries are merged, new ones are inserted keeping the entries
+/// sorted by key.
+pub fn merge_map<V: Layer + Clone>(
+    entries: &mut MapEntries<V>,
+    value: Value,
+    path: &str
+) -> Result<(), Error> {
+    let entries = entries.to_mut();
+    for (key, value) in table(value, path)? {
+        let path = child_path(path, &key);
+        match entries.binary_search_by(|(k, _)| (**k).cmp(key.as_str())) {
+            Ok(i) => entries[i].1.merge(value, &path)?,
+            Err(i) => entries.insert(i, (Cow::Owned(key), V::from_toml(value, &path)?))
+        }
+    }
+    Ok(())
+}
+
+/// Creates the error for a value of the wrong type.
+pub fn type_mismatch(value: &Value, path: &str, expected: &'static str) -> Error {
+    Error::TypeMismatch {
+        path: path.to_string(),
+        expected,
+        found: value.type_str()
+    }
+}
+
+impl Layer for bool {
+    fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+        match value {
+            Value::Boolean(b) => Ok(b),
+            value => Err(type_mismatch(&value, path, "boolean"))
+        }
+    }
+
+    fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+        if let Some(value) = parse_env(name, "boolean")? {
+            *self = value;
+        }
+        Ok(())
+    }
+}
+
+impl Layer for Cow<'static, str> {
+    fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+        match value {
+            Value::String(s) => Ok(Cow::Owned(s)),
+            value => Err(type_mismatch(&value, path, "string"))
+        }
+    }
+
+    fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+        if let Some(value) = env_var(name)? {
+            *self = Cow::Owned(value);
+        }
+        Ok(())
+    }
+}
+
+impl<T: Layer + Clone> Layer for Cow<'static, [T]> {
+    fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+        match value {
+            Value::Array(array) => array
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| T::from_toml(value, &child_path(path, i)))
+                .collect::<Result<Vec<_>, Error>>()
+                .map(Cow::Owned),
+            value => Err(type_mismatch(&value, path, "array"))
+        }
+    }
+
+    /// Items are addressed by their index, the length cannot change.
+    fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+        for (i, item) in self.to_mut().iter_mut().enumerate() {
+            item.merge_env(&env_name(name, i))?;
+        }
+        Ok(())
+    }
+}
+
+/// Empty arrays only accept empty arrays.
+impl Layer for [(); 0] {
+    fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+        tuple::<0>(value, path).map(|_| [])
+    }
+
+    fn merge_env(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Values of empty maps have no known type, so no entries can be added.
+impl Layer for () {
+    fn from_toml(_: Value, path: &str) -> Result<Self, Error> {
+        Err(Error::Unsupported(path.to_string()))
+    }
+
+    fn merge_env(&mut self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Optional fields of unified tables are merged if present, otherwise read.
+impl<T: Layer> Layer for Option<T> {
+    fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+        T::from_toml(value, path).map(Some)
+    }
+
+    fn merge(&mut self, value: Value, path: &str) -> Result<(), Error> {
+        match self {
+            Some(inner) => inner.merge(value, path),
+            None => {
+                *self = Some(T::from_toml(value, path)?);
+                Ok(())
+            }
+        }
+    }
+
+    /// Missing values cannot be created from environment variables.
+    fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+        match self {
+            Some(inner) => inner.merge_env(name),
+            None => Ok(())
+        }
+    }
+}
+
+macro_rules! impl_int {
+    ($($ty:ident),*) => {$(
+        impl Layer for $ty {
+            fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+                match value {
+                    Value::Integer(i) => i.try_into().map_err(|_| Error::ValueOutOfRange {
+                        path: path.to_string(),
+                        value: i,
+                        ty: stringify!($ty)
+                    }),
+                    value => Err(type_mismatch(&value, path, "integer"))
+                }
+            }
+
+            fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+                if let Some(value) = parse_env(name, stringify!($ty))? {
+                    *self = value;
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_float {
+    ($($ty:ident),*) => {$(
+        impl Layer for $ty {
+            fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+                // Integers of the embedded TOML may be overridden with float
+                // types, so overrides may be integers as well
+                match value {
+                    Value::Float(f) => Ok(f as $ty),
+                    Value::Integer(i) => Ok(i as $ty),
+                    value => Err(type_mismatch(&value, path, "float"))
+                }
+            }
+
+            fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+                if let Some(value) = parse_env(name, stringify!($ty))? {
+                    *self = value;
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+macro_rules! impl_datetime {
+    ($($ty:ident => $expected:literal),*) => {$(
+        impl Layer for $ty {
+            fn from_toml(value: Value, path: &str) -> Result<Self, Error> {
+                match &value {
+                    Value::Datetime(d) => d
+                        .to_string()
+                        .parse()
+                        .map_err(|_| type_mismatch(&value, path, $expected)),
+                    _ => Err(type_mismatch(&value, path, $expected))
+                }
+            }
+
+            fn merge_env(&mut self, name: &str) -> Result<(), Error> {
+                if let Some(value) = parse_env(name, $expected)? {
+                    *self = value;
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_datetime!(
+    LocalDate => "local date",
+    LocalTime => "local time",
+    LocalDatetime => "local datetime",
+    OffsetDatetime => "offset datetime"
+);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Read(path, e) => write!(f, "could not read {}, {e}", path.display()),
+            Error::Parse(path, e) => write!(f, "could not parse {}, {e}", path.display()),
+            Error::UnknownKey(path) => write!(f, "unknown key `{path}`"),
+            Error::MissingKey(path) => write!(f, "missing key `{path}`"),
+            Error::TypeMismatch {
+                path,
+                expected,
+                found
+            } => write!(f, "expected {expected} at `{path}`, found {found}"),
+            Error::ValueOutOfRange { path, value, ty } => {
+                write!(f, "value {value} at `{path}` is out of range for `{ty}`")
+            }
+            Error::LengthMismatch {
+                path,
+                expected,
+                found
+            } => write!(
+                f,
+                "expected array of {expected} values at `{path}`, found {found}"
+            ),
+            Error::Env {
+                name,
+                value,
+                expected
+            } => write!(
+                f,
+                "environment variable `{name}` with value `{value}` is no valid {expected}"
+            ),
+            Error::Unsupported(path) => write!(f, "value at `{path}` cannot be overridden")
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Read(_, e) => Some(e),
+            Error::Parse(_, e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primitives_work() {
+        assert_eq!(u16::from_toml(Value::Integer(8000), "port").unwrap(), 8000);
+        assert!(matches!(
+            u16::from_toml(Value::Integer(70000), "port"),
+            Err(Error::ValueOutOfRange { ty: "u16", .. })
+        ));
+        assert!(matches!(
+            bool::from_toml(Value::Integer(1), "enabled"),
+            Err(Error::TypeMismatch {
+                expected: "boolean",
+                found: "integer",
+                ..
+            })
+        ));
+
+        let date: Value = toml::from_str::<Table>("date = 1979-05-27").unwrap()["date"].clone();
+        assert_eq!(
+            LocalDate::from_toml(date.clone(), "date").unwrap(),
+            LocalDate::new(1979, 5, 27)
+        );
+        assert!(LocalTime::from_toml(date, "date").is_err());
+    }
+
+    #[test]
+    fn floats_accept_integers() {
+        assert_eq!(f64::from_toml(Value::Integer(3), "ratio").unwrap(), 3.0);
+        assert_eq!(f32::from_toml(Value::Float(0.5), "ratio").unwrap(), 0.5);
+        assert!(matches!(
+            f64::from_toml(Value::String("3".to_string()), "ratio"),
+            Err(Error::TypeMismatch {
+                expected: "float",
+                found: "string",
+                ..
+            })
+        ));
+
+        let mut ratio = 2.0f64;
+        ratio.merge(Value::Integer(3), "ratio").unwrap();
+        assert_eq!(ratio, 3.0);
+    }
+
+    #[test]
+    fn arrays_are_replaced() {
+        let mut ports: Cow<'static, [u16]> = Cow::Borrowed(&[8000, 8001]);
+        ports
+            .merge(Value::Array(vec![Value::Integer(9000)]), "ports")
+            .unwrap();
+        assert_eq!(&*ports, &[9000]);
+
+        let error = ports
+            .merge(Value::Array(vec![Value::Boolean(true)]), "ports")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected integer at `ports.0`, found boolean"
+        );
+
+        let error = tuple::<2>(Value::Array(vec![Value::Integer(1)]), "pair").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected array of 2 values at `pair`, found 1"
+        );
+    }
+
+    #[test]
+    fn maps_are_merged() {
+        let mut sizes: MapEntries<u8> =
+            Cow::Borrowed(&[(Cow::Borrowed("large"), 30), (Cow::Borrowed("small"), 10)]);
+        let table: Table = toml::from_str("medium = 20\nsmall = 5").unwrap();
+        merge_map(&mut sizes, Value::Table(table), "sizes").unwrap();
+        assert_eq!(&*sizes, &[
+            (Cow::Borrowed("large"), 30),
+            (Cow::Borrowed("medium"), 20),
+            (Cow::Borrowed("small"), 5)
+        ]);
+
+        let table: Table = toml::from_str("tiny = \"1\"").unwrap();
+        let error = merge_map(&mut sizes, Value::Table(table), "sizes").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected integer at `sizes.tiny`, found string"
+        );
+    }
+
+    #[test]
+    fn fields_work() {
+        let mut table: Table = toml::from_str("name = \"alpha\"\nrole = \"frontend\"").unwrap();
+        let name: Cow<'static, str> = field(&mut table, "name", "servers.0").unwrap();
+        assert_eq!(name, "alpha");
+        let port: Option<u16> = optional_field(&mut table, "port", "servers.0").unwrap();
+        assert_eq!(port, None);
+        assert!(matches!(
+            field::<u16>(&mut table, "port", "servers.0"),
+            Err(Error::MissingKey(path)) if path == "servers.0.port"
+        ));
+        assert_eq!(
+            deny_unknown(table, "servers.0").unwrap_err().to_string(),
+            "unknown key `servers.0.role`"
+        );
+
+        let mut port = None;
+        port.merge(Value::Integer(80), "port").unwrap();
+        assert_eq!(port, Some(80u16));
+    }
+
+    #[test]
+    fn env_overrides_work() {
+        assert_eq!(env_name("", "database"), "DATABASE");
+        assert_eq!(
+            env_name("APP_DATABASE", "temp-targets"),
+            "APP_DATABASE_TEMP_TARGETS"
+        );
+        assert_eq!(env_name("APP_PORTS", 0), "APP_PORTS_0");
+
+        env::set_var("STATIC_TOML_TEST_PORTS_1", "9001");
+        let mut ports: Cow<'static, [u16]> = Cow::Borrowed(&[8000, 8001]);
+        ports.merge_env("STATIC_TOML_TEST_PORTS").unwrap();
+        assert_eq!(&*ports, &[8000, 9001]);
+
+        env::set_var("STATIC_TOML_TEST_ENABLED", "yes");
+        let error = false.merge_env("STATIC_TOML_TEST_ENABLED").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "environment variable `STATIC_TOML_TEST_ENABLED` with value `yes` is no valid boolean"
+        );
+
+        env::set_var("STATIC_TOML_TEST_SIZES_SMALL", "5");
+        let mut sizes: MapEntries<u8> = Cow::Borrowed(&[(Cow::Borrowed("small"), 10)]);
+        merge_map_env(&mut sizes, "STATIC_TOML_TEST_SIZES").unwrap();
+        assert_eq!(sizes[0].1, 5);
+    }
+}